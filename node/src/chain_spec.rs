@@ -145,6 +145,7 @@ fn testnet_genesis(
 		aura: AuraConfig {
 			authorities: initial_authorities.iter().map(|x| (x.0.clone())).collect(),
 		},
+		assets: Default::default(),
 		grandpa: GrandpaConfig {
 			authorities: initial_authorities.iter().map(|x| (x.1.clone(), 1)).collect(),
 			..Default::default()
@@ -154,5 +155,7 @@ fn testnet_genesis(
 			key: Some(root_key),
 		},
 		transaction_payment: Default::default(),
+		treasury: Default::default(),
+		vesting: Default::default(),
 	}
 }
@@ -0,0 +1,17 @@
+//! Runtime API of the coin game pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+
+sp_api::decl_runtime_apis! {
+	/// Queries of the coin game state.
+	pub trait CoinGameApi<AccountId, Balance>
+	where
+		AccountId: Codec,
+		Balance: Codec,
+	{
+		/// Winnings credited to `who` and not claimed yet.
+		fn claimable(who: AccountId) -> Balance;
+	}
+}
@@ -0,0 +1,111 @@
+//! Types and helpers used to import rounds from a drand-style randomness beacon.
+//!
+//! A beacon publishes one pulse per round. For the chained drand scheme the pulse signature is a
+//! BLS12-381 signature (G2) over `sha256(previous_signature || round)` and the randomness of the
+//! round is `sha256(signature)`, so anyone holding the beacon public key can verify it.
+use frame_support::pallet_prelude::*;
+use sp_std::{marker::PhantomData, vec::Vec};
+
+/// A single round published by the beacon.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct BeaconPulse {
+	pub round: u64,
+	pub randomness: [u8; 32],
+	pub signature: [u8; 96],
+	pub previous_signature: [u8; 96],
+}
+
+impl BeaconPulse {
+	/// Check that the randomness of the pulse is derived from its signature.
+	pub fn randomness_matches_signature(&self) -> bool {
+		sp_io::hashing::sha2_256(&self.signature) == self.randomness
+	}
+
+	/// The message signed by the beacon for this round.
+	pub fn message(&self) -> [u8; 32] {
+		let mut message = Vec::with_capacity(self.previous_signature.len() + 8);
+		message.extend_from_slice(&self.previous_signature);
+		message.extend_from_slice(&self.round.to_be_bytes());
+		sp_io::hashing::sha2_256(&message)
+	}
+}
+
+/// Something that can check the signature of a beacon pulse.
+pub trait VerifyBeacon {
+	fn verify(pulse: &BeaconPulse) -> bool;
+}
+
+/// Domain separation tag used by drand when hashing messages to G2.
+const DRAND_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_";
+
+/// Verifies pulses of a chained drand network given its compressed G1 public key.
+pub struct DrandChainedVerifier<PublicKey>(PhantomData<PublicKey>);
+
+impl<PublicKey: Get<[u8; 48]>> VerifyBeacon for DrandChainedVerifier<PublicKey> {
+	fn verify(pulse: &BeaconPulse) -> bool {
+		use bls12_381::{
+			hash_to_curve::{ExpandMsgXmd, HashToCurve},
+			pairing, G1Affine, G2Affine, G2Projective,
+		};
+
+		let public: Option<G1Affine> = G1Affine::from_compressed(&PublicKey::get()).into();
+		let signature: Option<G2Affine> = G2Affine::from_compressed(&pulse.signature).into();
+		let (Some(public), Some(signature)) = (public, signature) else { return false };
+
+		let hashed = <G2Projective as HashToCurve<ExpandMsgXmd<sha2::Sha256>>>::hash_to_curve(
+			pulse.message(),
+			DRAND_DST,
+		);
+
+		pairing(&G1Affine::generator(), &signature) == pairing(&public, &G2Affine::from(hashed))
+	}
+}
+
+/// Parse the JSON body returned by `GET /public/{round}` on a drand HTTP endpoint.
+///
+/// Expected shape:
+/// `{"round":1,"randomness":"<hex>","signature":"<hex>","previous_signature":"<hex>"}`
+pub fn parse_pulse(body: &str) -> Option<BeaconPulse> {
+	use lite_json::{json::JsonValue, json_parser::parse_json};
+
+	let JsonValue::Object(fields) = parse_json(body).ok()? else { return None };
+
+	let field = |name: &str| {
+		fields
+			.iter()
+			.find(|(key, _)| key.iter().copied().eq(name.chars()))
+			.map(|(_, value)| value)
+	};
+	let hex_field = |name: &str| match field(name)? {
+		JsonValue::String(chars) => Some(chars.clone()),
+		_ => None,
+	};
+
+	let round = match field("round")? {
+		JsonValue::Number(number) if !number.negative && number.fraction_length == 0 =>
+			number.integer,
+		_ => return None,
+	};
+
+	Some(BeaconPulse {
+		round,
+		randomness: decode_hex(&hex_field("randomness")?)?,
+		signature: decode_hex(&hex_field("signature")?)?,
+		previous_signature: decode_hex(&hex_field("previous_signature")?)?,
+	})
+}
+
+fn decode_hex<const N: usize>(chars: &[char]) -> Option<[u8; N]> {
+	if chars.len() != N * 2 {
+		return None
+	}
+
+	let mut bytes = [0u8; N];
+	for (byte, pair) in bytes.iter_mut().zip(chars.chunks(2)) {
+		let high = pair[0].to_digit(16)?;
+		let low = pair[1].to_digit(16)?;
+		*byte = (high * 16 + low) as u8;
+	}
+
+	Some(bytes)
+}
@@ -1,35 +1,584 @@
-//! Benchmarking setup for pallet-template
+//! Benchmarking setup for pallet-coin-game
 #![cfg(feature = "runtime-benchmarks")]
 use super::*;
 
 #[allow(unused)]
-use crate::Pallet as Template;
+use crate::Pallet as CoinGame;
 use frame_benchmarking::v2::*;
+use frame_support::traits::{
+	fungible::MutateHold,
+	fungibles::{Inspect, Mutate},
+	Currency, EnsureOrigin,
+};
 use frame_system::RawOrigin;
+use sp_runtime::{
+	traits::{Bounded, One, SaturatedConversion, Saturating, Zero},
+	FixedU128, Permill,
+};
+use sp_std::vec::Vec;
+
+const SEED: u32 = 0;
+
+/// Round 72785 of the drand mainnet, which verifies against its public key.
+const DRAND_ROUND: u64 = 72785;
+const DRAND_PULSE: &str = r#"{"round":72785,"randomness":"8b676484b5fb1f37f9ec5c413d7d29883504e5b669f604a1ce68b3388e9ae3d9","signature":"82f5d3d2de4db19d40a6980e8aa37842a0e55d1df06bd68bddc8d60002e8e959eb9cfa368b3c1b77d18f02a54fe047b80f0989315f83b12a74fd8679c4f12aae86eaf6ab5690b34f1fddd50ee3cc6f6cdf59e95526d5a5d82aaa84fa6f181e42","previous_signature":"a609e19a03c2fcc559e8dae14900aaefe517cb55c840f6e69bc8e4f66c8d18e8a609685d9917efbfb0c37f058c2de88f13d297c7e19e0ab24813079efe57a182554ff054c7638153f9b26a60e7111f71a0ff63d9571704905d3ca6df0b031747"}"#;
+
+fn fund<T: Config>(who: &T::AccountId) {
+	T::Currency::make_free_balance_be(who, BalanceOf::<T>::max_value() / 4u32.into());
+}
+
+/// `count` existential deposits of the native currency
+fn units<T: Config>(count: u32) -> BalanceOf<T> {
+	T::Currency::minimum_balance().saturating_mul(count.into())
+}
+
+/// The whitelisted caller, funded along with the house
+fn funded_caller<T: Config>() -> T::AccountId {
+	let caller: T::AccountId = whitelisted_caller();
+	fund::<T>(&caller);
+	fund::<T>(&CoinGame::<T>::account_id());
+	caller
+}
+
+/// A funded caller holding a coin
+fn player<T: Config>() -> T::AccountId {
+	let caller = funded_caller::<T>();
+	CoinGame::<T>::do_create_coin(&caller, None).unwrap();
+	caller
+}
+
+/// Give `who` the pending winnings of a streak, expiring along with `others` streaks
+fn open_streak<T: Config>(who: &T::AccountId, others: u32) {
+	let pending = units::<T>(100);
+	let expires_at =
+		frame_system::Pallet::<T>::block_number().saturating_add(T::StreakExpiry::get());
+
+	let mut expiring: Vec<T::AccountId> =
+		(0..others).map(|i| account("player", i, SEED)).collect();
+	expiring.push(who.clone());
+	StreakExpiries::<T>::insert(expires_at, BoundedVec::truncate_from(expiring));
+	Streaks::<T>::insert(who, Streak { pending, length: 1, expires_at });
+	Committed::<T>::mutate(|committed| *committed = committed.saturating_add(pending));
+}
+
+/// Hold the stake of the wager `toss_id` of `who` waiting on `DRAND_ROUND` in escrow
+fn escrow_wager<T: Config>(who: &T::AccountId, toss_id: TossId, amount: BalanceOf<T>) {
+	T::NativeBalance::hold(&HoldReason::Escrow.into(), who, amount).unwrap();
+	let timeout_at = frame_system::Pallet::<T>::block_number();
+	Escrows::<T>::insert(
+		EscrowPurpose::Wager { round: DRAND_ROUND, toss_id },
+		who,
+		Escrow { amount, timeout_at },
+	);
+}
+
+/// An asset accepted for wagers, held by `owner` and by the house, whose bankroll exceeds the
+/// solvency buffer
+fn accepted_asset<T: Config>(owner: &T::AccountId) -> Result<AssetIdOf<T>, BenchmarkError> {
+	let asset = T::BenchmarkHelper::create_asset(owner);
+	T::Assets::mint_into(asset, owner, 1_000_000u32.into())?;
+	let bankroll = T::SolvencyBuffer::get()
+		.saturating_add(units::<T>(1_000_000))
+		.saturated_into::<u128>()
+		.saturated_into();
+	T::Assets::mint_into(asset, &CoinGame::<T>::account_id(), bankroll)?;
+	AssetBetLimits::<T>::insert(
+		asset,
+		BetLimits { min_bet: 1u32.into(), max_bet: 1_000u32.into(), native_rate: FixedU128::one() },
+	);
+	Ok(asset)
+}
 
 #[benchmarks]
 mod benchmarks {
 	use super::*;
 
 	#[benchmark]
-	fn do_something() {
-		let value = 100u32.into();
+	fn create_coin() {
 		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		// Every coin slot but one is taken
+		for i in 0..COIN_STORAGE_LIMIT - 1 {
+			let coin = Coin { side: CoinSide::Head, head_probability: Permill::from_percent(50) };
+			CoinGame::<T>::insert_coin(&account("player", i, SEED), coin);
+		}
+
 		#[extrinsic_call]
-		do_something(RawOrigin::Signed(caller), value);
+		_(RawOrigin::Signed(caller.clone()), None);
 
-		assert_eq!(Something::<T>::get(), Some(value));
+		assert!(CoinGame::<T>::get_coin(&caller).is_ok());
 	}
 
 	#[benchmark]
-	fn cause_error() {
-		Something::<T>::put(100u32);
-		let caller: T::AccountId = whitelisted_caller();
+	fn toss_coin() {
+		let caller = player::<T>();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), CoinSide::Head, units::<T>(100));
+
+		assert_eq!(NextTossId::<T>::get(), 1);
+	}
+
+	#[benchmark]
+	fn toss_many(b: Linear<1, { T::MaxBatch::get() }>) {
+		let caller = player::<T>();
+
+		let guesses: BoundedVec<CoinSide, T::MaxBatch> =
+			BoundedVec::truncate_from(sp_std::vec![CoinSide::Head; b as usize]);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), guesses, units::<T>(100));
+
+		assert_eq!(NextTossId::<T>::get(), b as u64);
+	}
+
+	#[benchmark]
+	fn remove_coin() {
+		let caller = player::<T>();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()));
+
+		assert!(CoinGame::<T>::get_coin(&caller).is_err());
+	}
+
+	#[benchmark]
+	fn toss_coin_with_beacon() {
+		let caller = player::<T>();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), CoinSide::Head);
+
+		assert_eq!(NextTossId::<T>::get(), 1);
+	}
+
+	/// A full global round, half of its players winning, and won batches of tosses make up the
+	/// `t` tosses resolved by the pulse, the heaviest import of a beacon round.
+	#[benchmark]
+	fn submit_beacon_pulse(
+		t: Linear<1, { T::MaxBeaconTosses::get() }>,
+	) -> Result<(), BenchmarkError> {
+		let caller = player::<T>();
+		let pulse = beacon::parse_pulse(DRAND_PULSE).unwrap();
+
+		let stake = units::<T>(100);
+		let head_probability = Permill::from_percent(50);
+		let mut wagers: Vec<BeaconWager<T>> = Vec::new();
+		let mut toss_id: TossId = 0;
+		let mut tosses = t;
+
+		let entries = T::MaxRoundEntries::get();
+		if tosses >= entries {
+			let round = 0;
+			let timeout_at = frame_system::Pallet::<T>::block_number();
+			let players: Vec<_> = (0..entries)
+				.map(|i| {
+					let player: T::AccountId = account("player", i, SEED);
+					fund::<T>(&player);
+					T::NativeBalance::hold(&HoldReason::Escrow.into(), &player, stake).unwrap();
+					Escrows::<T>::insert(
+						EscrowPurpose::Round(round),
+						&player,
+						Escrow { amount: stake, timeout_at },
+					);
+					let guess = if i % 2 == 0 { CoinSide::Head } else { CoinSide::Tail };
+					(player, guess, stake)
+				})
+				.collect();
+			RoundEntries::<T>::insert(round, BoundedVec::truncate_from(players));
+			wagers.push(BeaconWager {
+				toss_id,
+				who: CoinGame::<T>::account_id(),
+				kind: WagerKind::GlobalRound { round },
+			});
+			toss_id += 1;
+			tosses -= entries;
+		}
+
+		while tosses > 0 {
+			let len = tosses.min(T::MaxBatch::get());
+			let guesses: Vec<CoinSide> = (0..len as TossId)
+				.map(|index| {
+					CoinGame::<T>::beacon_coin_side(
+						&pulse.randomness,
+						toss_id + index,
+						head_probability,
+					)
+				})
+				.collect();
+			let payout = CoinGame::<T>::batch_payout_for(stake, head_probability, &guesses);
+			Committed::<T>::mutate(|committed| *committed = committed.saturating_add(payout));
+			escrow_wager::<T>(&caller, toss_id, stake.saturating_mul(len.into()));
+			wagers.push(BeaconWager {
+				toss_id,
+				who: caller.clone(),
+				kind: WagerKind::Batch {
+					guesses: BoundedVec::truncate_from(guesses),
+					head_probability,
+					stake,
+				},
+			});
+			toss_id += len as TossId;
+			tosses -= len;
+		}
+		let wagers = BoundedVec::try_from(wagers)
+			.map_err(|_| BenchmarkError::Stop("MaxBeaconWagers too low for MaxBeaconTosses"))?;
+		BeaconWagers::<T>::insert(DRAND_ROUND, wagers);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), pulse);
+
+		assert_eq!(LatestBeaconRound::<T>::get(), DRAND_ROUND);
+		assert!(!BeaconWagers::<T>::contains_key(DRAND_ROUND));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn roll() {
+		let caller = funded_caller::<T>();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), T::MaxSides::get(), 1, units::<T>(100));
+
+		assert_eq!(NextTossId::<T>::get(), 1);
+	}
+
+	#[benchmark]
+	fn open_series() {
+		let caller = funded_caller::<T>();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), CoinSide::Head, 1, units::<T>(100));
+
+		assert!(OpenSeries::<T>::contains_key(&caller));
+	}
+
+	#[benchmark]
+	fn toss_series() -> Result<(), BenchmarkError> {
+		let caller = funded_caller::<T>();
+		CoinGame::<T>::open_series(
+			RawOrigin::Signed(caller.clone()).into(),
+			CoinSide::Head,
+			1,
+			units::<T>(100),
+		)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()));
+
+		assert!(SeriesTosses::<T>::contains_key(&caller));
+		Ok(())
+	}
+
+	/// The pending winnings of a streak are rolled, unscheduling its expiry among the most
+	/// streaks expiring in the same block.
+	#[benchmark]
+	fn double_or_nothing(s: Linear<0, { T::MaxExpiringStreaks::get() - 1 }>) {
+		let caller = player::<T>();
+		open_streak::<T>(&caller, s);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), CoinSide::Head, None);
+
+		assert!(StreakTosses::<T>::contains_key(&caller));
+	}
+
+	/// The caller opens the round after `e` players joined it.
+	#[benchmark]
+	fn join_round(e: Linear<0, { T::MaxRoundEntries::get() - 1 }>) {
+		let caller = funded_caller::<T>();
+		let round = CoinGame::<T>::round_at(frame_system::Pallet::<T>::block_number());
+		let entries: Vec<_> = (0..e)
+			.map(|i| (account("player", i, SEED), CoinSide::Tail, units::<T>(100)))
+			.collect();
+		RoundEntries::<T>::insert(round, BoundedVec::truncate_from(entries));
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), CoinSide::Head, units::<T>(100));
+
+		assert_eq!(RoundEntries::<T>::get(round).len() as u32, e + 1);
+		assert!(RoundBeaconRounds::<T>::contains_key(round));
+	}
+
+	#[benchmark]
+	fn create_tournament() {
+		let caller = funded_caller::<T>();
+		let start_block = frame_system::Pallet::<T>::block_number().saturating_add(10u32.into());
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), units::<T>(10), T::MaxTournamentPlayers::get(), start_block);
+
+		assert!(Tournaments::<T>::contains_key(0));
+	}
+
+	#[benchmark]
+	fn register_for_tournament(
+		p: Linear<0, { T::MaxTournamentPlayers::get() - 1 }>,
+	) -> Result<(), BenchmarkError> {
+		let organizer: T::AccountId = account("organizer", 0, SEED);
+		fund::<T>(&organizer);
+		let start_block = frame_system::Pallet::<T>::block_number().saturating_add(10u32.into());
+		CoinGame::<T>::create_tournament(
+			RawOrigin::Signed(organizer).into(),
+			units::<T>(10),
+			T::MaxTournamentPlayers::get(),
+			start_block,
+		)?;
+		for i in 0..p {
+			let player: T::AccountId = account("player", i, SEED);
+			fund::<T>(&player);
+			CoinGame::<T>::register_for_tournament(RawOrigin::Signed(player).into(), 0)?;
+		}
+		let caller = funded_caller::<T>();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), 0);
+
+		assert_eq!(TournamentPlayers::<T>::get(0).len() as u32, p + 1);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn cancel_tournament(
+		p: Linear<0, { T::MaxTournamentPlayers::get() }>,
+	) -> Result<(), BenchmarkError> {
+		let caller = funded_caller::<T>();
+		let start_block = frame_system::Pallet::<T>::block_number().saturating_add(10u32.into());
+		CoinGame::<T>::create_tournament(
+			RawOrigin::Signed(caller.clone()).into(),
+			units::<T>(10),
+			T::MaxTournamentPlayers::get(),
+			start_block,
+		)?;
+		for i in 0..p {
+			let player: T::AccountId = account("player", i, SEED);
+			fund::<T>(&player);
+			CoinGame::<T>::register_for_tournament(RawOrigin::Signed(player).into(), 0)?;
+		}
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), 0);
+
+		assert!(!TournamentPlayers::<T>::contains_key(0));
+		assert!(ActiveTournaments::<T>::get().is_empty());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn buy_tickets(c: Linear<1, { T::MaxTickets::get() }>) {
+		let caller = funded_caller::<T>();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), c);
+
+		assert_eq!(LotteryPot::<T>::get(), T::TicketPrice::get().saturating_mul(c.into()));
+	}
+
+	#[benchmark]
+	fn fund_house() {
+		let caller = funded_caller::<T>();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), units::<T>(1_000));
+
+		assert!(!TotalShares::<T>::get().is_zero());
+	}
+
+	#[benchmark]
+	fn withdraw_from_house() -> Result<(), BenchmarkError> {
+		let origin = T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		fund::<T>(&CoinGame::<T>::account_id());
+		let dest: T::AccountId = account("dest", 0, SEED);
+		let amount = units::<T>(1_000);
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, dest.clone(), amount);
+
+		assert_eq!(T::Currency::free_balance(&dest), amount);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_wagering_paused() -> Result<(), BenchmarkError> {
+		let origin = T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, true);
+
+		assert!(WageringPaused::<T>::get());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn provide_liquidity() {
+		let caller = funded_caller::<T>();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), units::<T>(1_000_000));
+
+		assert!(!LiquidityShares::<T>::get(&caller).is_zero());
+	}
+
+	/// A pending request is replaced.
+	#[benchmark]
+	fn withdraw_liquidity() -> Result<(), BenchmarkError> {
+		let caller = funded_caller::<T>();
+		CoinGame::<T>::provide_liquidity(
+			RawOrigin::Signed(caller.clone()).into(),
+			units::<T>(1_000_000),
+		)?;
+		let shares = LiquidityShares::<T>::get(&caller);
+		CoinGame::<T>::withdraw_liquidity(
+			RawOrigin::Signed(caller.clone()).into(),
+			shares / 2u32.into(),
+		)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), shares);
+
+		assert_eq!(LiquidityWithdrawals::<T>::get(&caller).map(|w| w.shares), Some(shares));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn redeem_liquidity() -> Result<(), BenchmarkError> {
+		let caller = funded_caller::<T>();
+		CoinGame::<T>::provide_liquidity(
+			RawOrigin::Signed(caller.clone()).into(),
+			units::<T>(1_000_000),
+		)?;
+		let shares = LiquidityShares::<T>::get(&caller);
+		CoinGame::<T>::withdraw_liquidity(RawOrigin::Signed(caller.clone()).into(), shares)?;
+		let unlock_at = frame_system::Pallet::<T>::block_number()
+			.saturating_add(T::LiquidityWithdrawalDelay::get());
+		frame_system::Pallet::<T>::set_block_number(unlock_at);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()));
+
+		assert!(!LiquidityWithdrawals::<T>::contains_key(&caller));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn toss_coin_with_asset() -> Result<(), BenchmarkError> {
+		let caller = player::<T>();
+		let asset = accepted_asset::<T>(&caller)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), asset, CoinSide::Head, 100u32.into());
+
+		assert!(!AssetCommitted::<T>::get(asset).is_zero());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_asset_bet_limits() -> Result<(), BenchmarkError> {
+		let origin = T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let owner: T::AccountId = account("owner", 0, SEED);
+		let asset = T::BenchmarkHelper::create_asset(&owner);
+		let limits =
+			BetLimits { min_bet: 1u32.into(), max_bet: 1_000u32.into(), native_rate: FixedU128::one() };
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, asset, Some(limits));
+
+		assert!(AssetBetLimits::<T>::contains_key(asset));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn fund_asset_house() -> Result<(), BenchmarkError> {
+		let caller = funded_caller::<T>();
+		let asset = accepted_asset::<T>(&caller)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), asset, 1_000u32.into());
+
+		assert_eq!(T::Assets::balance(asset, &CoinGame::<T>::account_id()), 1_001_000u32.into());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn withdraw_asset_from_house() -> Result<(), BenchmarkError> {
+		let origin = T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let owner: T::AccountId = account("owner", 0, SEED);
+		let asset = accepted_asset::<T>(&owner)?;
+		let dest: T::AccountId = account("dest", 0, SEED);
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, asset, dest.clone(), 1_000u32.into());
+
+		assert_eq!(T::Assets::balance(asset, &dest), 1_000u32.into());
+		Ok(())
+	}
+
+	/// Looser limits are scheduled on top of the stricter ones in effect.
+	#[benchmark]
+	fn set_play_limits() -> Result<(), BenchmarkError> {
+		let caller = funded_caller::<T>();
+		let limits = |cap: u32| PlayLimits {
+			daily_loss_cap: Some(units::<T>(cap)),
+			weekly_loss_cap: Some(units::<T>(cap)),
+			stake_cap: Some(units::<T>(cap)),
+		};
+		CoinGame::<T>::set_play_limits(RawOrigin::Signed(caller.clone()).into(), limits(10))?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), limits(1_000));
+
+		assert!(PendingPlayerLimits::<T>::contains_key(&caller));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn self_exclude() {
+		let caller = funded_caller::<T>();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), 10u32.into());
+
+		assert!(SelfExclusions::<T>::contains_key(&caller));
+	}
+
+	/// Every winning vests, adding a vesting schedule.
+	#[benchmark]
+	fn claim() {
+		let caller = funded_caller::<T>();
+		let amount = units::<T>(10_000);
+		PayoutVestingRule::<T>::put(PayoutVesting { threshold: units::<T>(1), duration: 100u32.into() });
+		Claimable::<T>::insert(&caller, amount);
+		VestingClaimable::<T>::insert(&caller, amount);
+		Committed::<T>::put(amount);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), None);
+
+		assert!(!Claimable::<T>::contains_key(&caller));
+	}
+
+	#[benchmark]
+	fn set_payout_vesting() -> Result<(), BenchmarkError> {
+		let origin = T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let rule = PayoutVesting { threshold: units::<T>(1_000), duration: 100u32.into() };
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, Some(rule));
+
+		assert!(PayoutVestingRule::<T>::get().is_some());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn cash_out_streak(s: Linear<0, { T::MaxExpiringStreaks::get() - 1 }>) {
+		let caller = funded_caller::<T>();
+		open_streak::<T>(&caller, s);
+
 		#[extrinsic_call]
-		cause_error(RawOrigin::Signed(caller));
+		_(RawOrigin::Signed(caller.clone()));
 
-		assert_eq!(Something::<T>::get(), Some(101u32));
+		assert!(!Streaks::<T>::contains_key(&caller));
 	}
 
-	impl_benchmark_test_suite!(Template, crate::mock::new_test_ext(), crate::mock::Test);
+	impl_benchmark_test_suite!(CoinGame, crate::mock::new_test_ext(), crate::mock::Test);
 }
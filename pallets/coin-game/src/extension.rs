@@ -0,0 +1,105 @@
+//! Transaction extension rejecting coin game calls that are known to fail before they reach a
+//! block, so that their senders don't pay fees for them: tosses without a coin or over the rate
+//! limit, and coins created twice.
+use crate::{Call, Config, Pallet};
+use codec::{Decode, Encode};
+use frame_support::{dispatch::DispatchInfo, traits::IsSubType};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{DispatchInfoOf, Dispatchable, SignedExtension},
+	transaction_validity::{
+		InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction,
+	},
+};
+use sp_std::{fmt, marker::PhantomData};
+
+/// Custom validity error: the account tossed too recently or too often.
+pub const TOO_SOON: u8 = 1;
+/// Custom validity error: the call needs a coin and the account has none.
+pub const COIN_NOT_FOUND: u8 = 2;
+/// Custom validity error: the account already has a coin.
+pub const COIN_ALREADY_EXISTS: u8 = 3;
+
+/// Checks the preconditions of coin game calls in the transaction pool.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct CheckCoinGame<T: Config + Send + Sync>(PhantomData<T>);
+
+impl<T: Config + Send + Sync> CheckCoinGame<T> {
+	pub fn new() -> Self {
+		Self(PhantomData)
+	}
+}
+
+impl<T: Config + Send + Sync> Default for CheckCoinGame<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T: Config + Send + Sync> fmt::Debug for CheckCoinGame<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "CheckCoinGame")
+	}
+}
+
+impl<T: Config + Send + Sync> SignedExtension for CheckCoinGame<T>
+where
+	T::RuntimeCall: Dispatchable<Info = DispatchInfo> + IsSubType<Call<T>>,
+{
+	const IDENTIFIER: &'static str = "CheckCoinGame";
+	type AccountId = T::AccountId;
+	type Call = T::RuntimeCall;
+	type AdditionalSigned = ();
+	type Pre = ();
+
+	fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+		Ok(())
+	}
+
+	fn validate(
+		&self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		_info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> TransactionValidity {
+		let Some(call) = call.is_sub_type() else { return Ok(ValidTransaction::default()) };
+
+		let has_coin = Pallet::<T>::get_coin(who).is_ok();
+		let (needs_coin, tosses) = match call {
+			Call::create_coin { .. } => {
+				if has_coin {
+					return Err(InvalidTransaction::Custom(COIN_ALREADY_EXISTS).into())
+				}
+				(false, 0)
+			},
+			Call::toss_coin { .. } |
+			Call::toss_coin_with_beacon { .. } |
+			Call::toss_coin_with_asset { .. } => (true, 1),
+			Call::toss_many { guesses, .. } => (true, guesses.len() as u32),
+			Call::remove_coin { .. } | Call::double_or_nothing { .. } => (true, 0),
+			_ => (false, 0),
+		};
+
+		if needs_coin && !has_coin {
+			return Err(InvalidTransaction::Custom(COIN_NOT_FOUND).into())
+		}
+		if tosses > 0 {
+			Pallet::<T>::check_toss_rate(who, tosses)
+				.map_err(|_| InvalidTransaction::Custom(TOO_SOON))?;
+		}
+
+		Ok(ValidTransaction::default())
+	}
+
+	fn pre_dispatch(
+		self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		self.validate(who, call, info, len).map(|_| ())
+	}
+}
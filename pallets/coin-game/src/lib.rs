@@ -1,5 +1,7 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+
 /// Edit this file to define custom logic or remove it if it is not needed.
 /// Learn more about FRAME and the core library of Substrate FRAME pallets:
 /// <https://docs.substrate.io/reference/frame-pallets/>
@@ -13,27 +15,103 @@ mod tests;
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
+pub mod beacon;
+pub mod extension;
+pub mod migrations;
 pub mod weights;
 pub use weights::*;
+pub use extension::CheckCoinGame;
+
+use sp_core::crypto::KeyTypeId;
+
+const LOG_TARGET: &str = "runtime::coin-game";
+
+/// Key type used by the off-chain worker to sign beacon submissions.
+pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"coin");
+
+/// Crypto used by the off-chain worker to sign beacon submissions. The keys are expected to be
+/// inserted in the node keystore under [`KEY_TYPE`].
+pub mod crypto {
+	use super::KEY_TYPE;
+	use sp_core::sr25519::Signature as Sr25519Signature;
+	use sp_runtime::{
+		app_crypto::{app_crypto, sr25519},
+		traits::Verify,
+		MultiSignature, MultiSigner,
+	};
+	app_crypto!(sr25519, KEY_TYPE);
+
+	pub struct BeaconAuthId;
+
+	impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for BeaconAuthId {
+		type RuntimeAppPublic = Public;
+		type GenericSignature = sp_core::sr25519::Signature;
+		type GenericPublic = sp_core::sr25519::Public;
+	}
+
+	impl frame_system::offchain::AppCrypto<<Sr25519Signature as Verify>::Signer, Sr25519Signature>
+		for BeaconAuthId
+	{
+		type RuntimeAppPublic = Public;
+		type GenericSignature = sp_core::sr25519::Signature;
+		type GenericPublic = sp_core::sr25519::Public;
+	}
+}
+
+/// Creates the assets wagered in the benchmarks, which the pallet cannot create by itself.
+#[cfg(feature = "runtime-benchmarks")]
+pub trait BenchmarkHelper<AssetId, AccountId> {
+	/// Create a sufficient asset owned by `owner` and return its id.
+	fn create_asset(owner: &AccountId) -> AssetId;
+}
 
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
+	use crate::beacon::{self, BeaconPulse, VerifyBeacon};
+	use alloc::format;
 	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::*;
+	use frame_system::offchain::{
+		AppCrypto, CreateSignedTransaction, SendSignedTransaction, Signer, SubmitTransaction,
+	};
 	use frame_support::BoundedBTreeMap;
 
-	use frame_support::PalletId;
-	use frame_support::traits::Randomness;
+	use frame_support::{dispatch::Pays, storage::with_storage_layer, PalletId};
+	use frame_support::traits::{
+		fungible::{self, InspectHold, MutateHold},
+		fungibles,
+		tokens::{Fortitude, Precision, Preservation, Restriction},
+		Currency, ExistenceRequirement, OnKilledAccount, OnUnbalanced, Randomness, UnixTime,
+		VestingSchedule, WithdrawReasons,
+	};
+	use sp_runtime::{
+		offchain::{http, storage::StorageValueRef, Duration},
+		helpers_128bit::multiply_by_rational_with_rounding,
+		traits::{AccountIdConversion, CheckedSub, Hash, One, SaturatedConversion, Saturating, Zero},
+		FixedPointNumber, FixedU128, PerThing, Permill, Rounding,
+	};
+	use sp_std::vec::Vec;
+
+	/// The in-code storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
 
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
+	pub type BalanceOf<T> = <<T as Config>::Currency as Currency<AccountIdOf<T>>>::Balance;
+	pub type AssetIdOf<T> =
+		<<T as Config>::Assets as fungibles::Inspect<AccountIdOf<T>>>::AssetId;
+	pub type AssetBalanceOf<T> =
+		<<T as Config>::Assets as fungibles::Inspect<AccountIdOf<T>>>::Balance;
+	pub type NegativeImbalanceOf<T> =
+		<<T as Config>::Currency as Currency<AccountIdOf<T>>>::NegativeImbalance;
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
 	#[pallet::config]
-	pub trait Config: frame_system::Config {
+	pub trait Config: CreateSignedTransaction<Call<Self>> + frame_system::Config {
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 		/// Type representing the weight of this pallet
@@ -43,6 +121,194 @@ pub mod pallet {
 		type PalletId: Get<PalletId>;
 		/// Type representing the random number generator
 		type Randomness: Randomness<Self::Hash, BlockNumberFor<Self>>;
+		/// Type representing the currency used for wagers
+		type Currency: Currency<Self::AccountId>;
+		/// Type representing the assets that can be wagered besides the native currency
+		type Assets: fungibles::Mutate<Self::AccountId>;
+		/// Type holding coin deposits and the stakes of pending wagers, the native currency
+		type NativeBalance: fungible::MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason>
+			+ fungible::Inspect<Self::AccountId, Balance = BalanceOf<Self>>;
+		/// Overarching hold reason
+		type RuntimeHoldReason: From<HoldReason>;
+		/// Type paying out large winnings on a vesting schedule
+		type Vesting: VestingSchedule<
+			Self::AccountId,
+			Moment = BlockNumberFor<Self>,
+			Currency = Self::Currency,
+		>;
+		/// Deposit held from the owner of a coin while it occupies a coin slot
+		#[pallet::constant]
+		type CoinDeposit: Get<BalanceOf<Self>>;
+		/// Blocks without activity after which a coin is removed and its deposit returned
+		#[pallet::constant]
+		type CoinExpiry: Get<BlockNumberFor<Self>>;
+		/// Origin allowed to withdraw from the house and to pause wagering
+		type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// Largest stake accepted on a house-backed wager, as a share of the available bankroll
+		#[pallet::constant]
+		type MaxBetFraction: Get<Permill>;
+		/// Part of the house balance never put at risk by wagers or withdrawals
+		#[pallet::constant]
+		type SolvencyBuffer: Get<BalanceOf<Self>>;
+		/// Available bankroll under which wagering is paused until the admin resumes it
+		#[pallet::constant]
+		type CircuitBreakerThreshold: Get<BalanceOf<Self>>;
+		/// Share of every house-backed stake charged as rake on top of the stake
+		#[pallet::constant]
+		type Rake: Get<Permill>;
+		/// Handler of the rake, e.g. a treasury
+		type RakeDestination: OnUnbalanced<NegativeImbalanceOf<Self>>;
+		/// Blocks between a liquidity withdrawal request and the redemption of its shares
+		#[pallet::constant]
+		type LiquidityWithdrawalDelay: Get<BlockNumberFor<Self>>;
+		/// Blocks after the withdrawal delay during which the shares can be redeemed, after
+		/// which the request expires and the shares are given back
+		#[pallet::constant]
+		type LiquidityRedemptionWindow: Get<BlockNumberFor<Self>>;
+		/// Shares locked in the house when the first shares of a worthless house are minted
+		#[pallet::constant]
+		type MinimumLiquidity: Get<BalanceOf<Self>>;
+		/// Blocks after the expected resolution of a wager at which its escrowed stakes are
+		/// refunded if it is still unresolved
+		#[pallet::constant]
+		type EscrowTimeout: Get<BlockNumberFor<Self>>;
+		/// Maximum number of escrows timing out in the same block
+		#[pallet::constant]
+		type MaxEscrowTimeouts: Get<u32>;
+		/// Maximum number of sides of a dice
+		#[pallet::constant]
+		type MaxSides: Get<u32>;
+		/// Maximum number of tosses resolved by a single `toss_many` call
+		#[pallet::constant]
+		type MaxBatch: Get<u32>;
+		/// Blocks an account has to wait between two tosses
+		#[pallet::constant]
+		type TossCooldown: Get<BlockNumberFor<Self>>;
+		/// Blocks of the periods over which tosses are counted
+		#[pallet::constant]
+		type TossPeriod: Get<BlockNumberFor<Self>>;
+		/// Maximum number of tosses of an account in a period
+		#[pallet::constant]
+		type MaxTossesPerPeriod: Get<u32>;
+		/// Tosses a coin holder can make without paying the transaction fee in a free toss period
+		#[pallet::constant]
+		type FreeTosses: Get<u32>;
+		/// Blocks after which the free tosses of every coin holder are refreshed
+		#[pallet::constant]
+		type FreeTossPeriod: Get<BlockNumberFor<Self>>;
+		/// Blocks in a day, the period of daily loss caps. Weekly loss caps span seven days.
+		#[pallet::constant]
+		type DayLength: Get<BlockNumberFor<Self>>;
+		/// Blocks before looser play limits take effect
+		#[pallet::constant]
+		type LimitLooseningDelay: Get<BlockNumberFor<Self>>;
+		/// Maximum number of tosses of a best-of-N series
+		#[pallet::constant]
+		type MaxSeriesLength: Get<u32>;
+		/// Blocks after which the stake of a series still open is refunded
+		#[pallet::constant]
+		type SeriesExpiry: Get<BlockNumberFor<Self>>;
+		/// Number of wins after which a double-or-nothing streak is paid out
+		#[pallet::constant]
+		type MaxStreakLength: Get<u32>;
+		/// Blocks after which the winnings of an idle streak are paid out
+		#[pallet::constant]
+		type StreakExpiry: Get<BlockNumberFor<Self>>;
+		/// Maximum number of streaks expiring in the same block
+		#[pallet::constant]
+		type MaxExpiringStreaks: Get<u32>;
+		/// Blocks during which players can join a global round. The round is resolved by the beacon
+		/// round published `BeaconRoundDelay` rounds after the end of the window.
+		#[pallet::constant]
+		type RoundLength: Get<BlockNumberFor<Self>>;
+		/// Share of the losers' pool of a global round kept by the house
+		#[pallet::constant]
+		type RoundFee: Get<Permill>;
+		/// Maximum number of players of a global round
+		#[pallet::constant]
+		type MaxRoundEntries: Get<u32>;
+		/// Maximum number of players of a tournament
+		#[pallet::constant]
+		type MaxTournamentPlayers: Get<u32>;
+		/// Maximum number of tournaments open or in progress at the same time
+		#[pallet::constant]
+		type MaxActiveTournaments: Get<u32>;
+		/// Deposit held from the organizer of a tournament until it starts or is cancelled
+		#[pallet::constant]
+		type TournamentDeposit: Get<BalanceOf<Self>>;
+		/// Most blocks between the creation of a tournament and its start
+		#[pallet::constant]
+		type MaxRegistrationPeriod: Get<BlockNumberFor<Self>>;
+		/// Share of the prize pool of a tournament paid to the champion, the rest goes to the
+		/// runner-up
+		#[pallet::constant]
+		type TournamentWinnerShare: Get<Permill>;
+		/// Blocks during which lottery tickets are sold before the draw
+		#[pallet::constant]
+		type LotteryPeriod: Get<BlockNumberFor<Self>>;
+		/// Price of a lottery ticket
+		#[pallet::constant]
+		type TicketPrice: Get<BalanceOf<Self>>;
+		/// Maximum number of tickets sold for a single draw
+		#[pallet::constant]
+		type MaxTickets: Get<u32>;
+		/// Share of the lottery pot kept by the house when a winner is drawn
+		#[pallet::constant]
+		type LotteryHouseCut: Get<Permill>;
+		/// Share of every lost stake of a coin toss, dice roll or series added to the jackpot
+		#[pallet::constant]
+		type JackpotContribution: Get<Permill>;
+		/// Consecutive staked coin tosses to guess right to win the jackpot
+		#[pallet::constant]
+		type JackpotStreak: Get<u32>;
+		/// Smallest stake of a coin toss counting towards the jackpot
+		#[pallet::constant]
+		type MinJackpotStake: Get<BalanceOf<Self>>;
+		/// Identifier used by the off-chain worker to sign beacon submissions
+		type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
+		/// Type used to verify the signature of beacon pulses
+		type BeaconVerifier: VerifyBeacon;
+		/// Type providing the current time, used to know which beacon round is being published
+		type UnixTime: UnixTime;
+		/// HTTP endpoint of the randomness beacon, e.g. `https://api.drand.sh`
+		type BeaconEndpoint: Get<&'static str>;
+		/// Unix time in seconds at which the beacon published its first round
+		#[pallet::constant]
+		type BeaconGenesisTime: Get<u64>;
+		/// Seconds between two beacon rounds
+		#[pallet::constant]
+		type BeaconPeriod: Get<u64>;
+		/// Number of rounds between a wager and the beacon round that resolves it
+		#[pallet::constant]
+		type BeaconRoundDelay: Get<u64>;
+		/// Expected milliseconds between two blocks, used to tell the block at which a beacon
+		/// round is published
+		#[pallet::constant]
+		type ExpectedBlockTime: Get<u64>;
+		/// Maximum number of wagers waiting on the same beacon round
+		#[pallet::constant]
+		type MaxBeaconWagers: Get<u32>;
+		/// Maximum number of tosses, counting every player of a global round, resolved by the
+		/// same beacon round. Bounds the weight of importing a round.
+		#[pallet::constant]
+		type MaxBeaconTosses: Get<u32>;
+		/// Priority of the unsigned transactions submitting beacon pulses
+		#[pallet::constant]
+		type BeaconUnsignedPriority: Get<TransactionPriority>;
+		/// Helper creating the assets wagered in the benchmarks
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: BenchmarkHelper<AssetIdOf<Self>, Self::AccountId>;
+	}
+
+	/// Reasons for the pallet to hold funds of an account
+	#[pallet::composite_enum]
+	pub enum HoldReason {
+		/// Deposit of a coin occupying a coin slot
+		CoinDeposit,
+		/// Stake of a wager waiting for its resolution
+		Escrow,
+		/// Deposit of a tournament occupying a tournament slot
+		TournamentDeposit,
 	}
 
 	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo, PartialOrd)]
@@ -51,30 +317,853 @@ pub mod pallet {
 		Tail,
 	}
 
+	impl CoinSide {
+		/// A coin is a two sided dice, where the first outcome is `Head`.
+		pub fn from_outcome(outcome: u32) -> Self {
+			if outcome == 0 {
+				CoinSide::Head
+			} else {
+				CoinSide::Tail
+			}
+		}
+
+		/// Side of a biased coin given an outcome drawn uniformly in `0..Permill::ACCURACY`.
+		pub fn from_biased_outcome(outcome: u32, head_probability: Permill) -> Self {
+			if outcome < head_probability.deconstruct() {
+				CoinSide::Head
+			} else {
+				CoinSide::Tail
+			}
+		}
+	}
+
 	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo, PartialOrd)]
 	pub struct Coin {
 		pub side: CoinSide,
+		/// Probability of the coin landing on `Head`, one half for a fair coin
+		pub head_probability: Permill,
+	}
+
+	impl Coin {
+		pub fn fair(side: CoinSide) -> Self {
+			Coin { side, head_probability: Permill::from_percent(50) }
+		}
+	}
+
+	/// A best-of-N series of fair coin tosses played for a single stake
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	pub struct Series<Balance> {
+		/// Side the player expects to win the series
+		pub guess: CoinSide,
+		/// Number of tosses of the series, always odd
+		pub length: u32,
+		pub stake: Balance,
+		pub heads: u32,
+		pub tails: u32,
+	}
+
+	impl<Balance> Series<Balance> {
+		/// Tosses a side needs to win the series.
+		pub fn majority(&self) -> u32 {
+			self.length / 2 + 1
+		}
+
+		/// Side that won the series, if any reached the majority.
+		pub fn winner(&self) -> Option<CoinSide> {
+			if self.heads >= self.majority() {
+				Some(CoinSide::Head)
+			} else if self.tails >= self.majority() {
+				Some(CoinSide::Tail)
+			} else {
+				None
+			}
+		}
+	}
+
+	/// Identifier of a coin toss, unique across every game mode
+	pub type TossId = u64;
+
+	/// Randomness a toss was resolved with, so that it can be verified off-chain
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub enum RandomnessSource<BlockNumber> {
+		/// `T::Randomness` queried in `block` with `subject`
+		Block { block: BlockNumber, subject: Vec<u8> },
+		/// Randomness of an imported beacon round, hashed with the id of the toss
+		Beacon { round: u64 },
+	}
+
+	/// Wager of a player waiting for the randomness of a beacon round. Its stake is held in
+	/// escrow, or in the house for an asset, and its payout committed until it is resolved, or
+	/// refunded when the beacon round does not come in time.
+	#[derive(
+		CloneNoBound,
+		Encode,
+		Decode,
+		EqNoBound,
+		PartialEqNoBound,
+		RuntimeDebugNoBound,
+		MaxEncodedLen,
+		TypeInfo,
+	)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct BeaconWager<T: Config> {
+		/// Id of the toss resolving the wager, or of the first one for a batch
+		pub toss_id: TossId,
+		pub who: AccountIdOf<T>,
+		pub kind: WagerKind<T>,
+	}
+
+	/// Game a wager waiting for the beacon is placed on
+	#[derive(
+		CloneNoBound,
+		Encode,
+		Decode,
+		EqNoBound,
+		PartialEqNoBound,
+		RuntimeDebugNoBound,
+		MaxEncodedLen,
+		TypeInfo,
+	)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub enum WagerKind<T: Config> {
+		/// Coin toss, free when nothing is staked
+		Toss {
+			guess: CoinSide,
+			head_probability: Permill,
+			stake: BalanceOf<T>,
+			payout: BalanceOf<T>,
+		},
+		/// Coin tosses staking `stake` on each guess
+		Batch {
+			guesses: BoundedVec<CoinSide, T::MaxBatch>,
+			head_probability: Permill,
+			stake: BalanceOf<T>,
+		},
+		/// Dice roll
+		Roll { sides: u32, guess: u32, stake: BalanceOf<T>, payout: BalanceOf<T> },
+		/// Double-or-nothing toss of a streak that won `length` tosses so far
+		DoubleOrNothing {
+			guess: CoinSide,
+			head_probability: Permill,
+			stake: BalanceOf<T>,
+			payout: BalanceOf<T>,
+			length: u32,
+		},
+		/// Coin toss staked in an asset
+		AssetToss {
+			asset: AssetIdOf<T>,
+			guess: CoinSide,
+			head_probability: Permill,
+			stake: AssetBalanceOf<T>,
+			payout: AssetBalanceOf<T>,
+		},
+		/// Next toss of the open series of the player
+		SeriesToss,
+		/// Flip resolving a global round, placed by the house
+		GlobalRound { round: u32 },
+	}
+
+	impl<T: Config> WagerKind<T> {
+		/// Tosses, or payouts of a global round, the resolution of the wager scales with.
+		pub fn tosses(&self) -> u32 {
+			match self {
+				WagerKind::Batch { guesses, .. } => guesses.len() as u32,
+				WagerKind::GlobalRound { .. } => T::MaxRoundEntries::get(),
+				_ => 1,
+			}
+		}
+
+		/// Native stake held in escrow until the wager is resolved. The winnings rolled into a
+		/// streak are already in the house.
+		pub fn escrowed(&self) -> BalanceOf<T> {
+			match self {
+				WagerKind::Toss { stake, .. } |
+				WagerKind::Roll { stake, .. } |
+				WagerKind::DoubleOrNothing { stake, length: 0, .. } => *stake,
+				WagerKind::Batch { guesses, stake, .. } =>
+					stake.saturating_mul((guesses.len() as u32).into()),
+				_ => Zero::zero(),
+			}
+		}
+	}
+
+	/// Winnings of a double-or-nothing streak, held by the house until rolled or cashed out
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	pub struct Streak<Balance, BlockNumber> {
+		pub pending: Balance,
+		/// Number of tosses won in a row
+		pub length: u32,
+		/// Block at which the pending winnings are paid out if the streak is left idle
+		pub expires_at: BlockNumber,
+	}
+
+	/// Outcome of a global round
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	pub struct RoundResult<Balance> {
+		pub result: CoinSide,
+		/// Number of players that guessed the result
+		pub winners: u32,
+		/// Stakes of the players that guessed the result
+		pub winners_pool: Balance,
+		/// Stakes of the players that did not guess the result
+		pub losers_pool: Balance,
+		/// Part of the losers' pool kept by the house
+		pub fee: Balance,
+	}
+
+	pub type TournamentId = u32;
+
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	pub enum TournamentStatus {
+		/// Players can register until the start block
+		Registration,
+		/// The given bracket round is played next
+		InProgress { round: u32 },
+		Finished,
+		/// Cancelled by the organizer or for lack of players, entry fees were refunded
+		Cancelled,
+	}
+
+	/// A knockout tournament resolved by coin flips
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	pub struct Tournament<AccountId, Balance, BlockNumber> {
+		pub organizer: AccountId,
+		pub entry_fee: Balance,
+		pub max_players: u32,
+		pub start_block: BlockNumber,
+		/// Held from the organizer until the tournament starts or is cancelled
+		pub deposit: Balance,
+		pub status: TournamentStatus,
+		/// Entry fees held so far, moved to the house at the start and distributed to the top
+		/// finishers
+		pub prize_pool: Balance,
+	}
+
+	/// Wager a stake is held in escrow for
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	pub enum EscrowPurpose {
+		/// Stake of a global round
+		Round(u32),
+		/// Entry fee of a tournament
+		Tournament(TournamentId),
+		/// Stake of a wager waiting for a beacon round
+		Wager { round: u64, toss_id: TossId },
+		/// Stake of an open best-of-N series
+		Series,
+	}
+
+	/// Stake held in the account of a player until its wager is resolved
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	pub struct Escrow<Balance, BlockNumber> {
+		pub amount: Balance,
+		/// Block at which the stake is refunded if the wager is still unresolved
+		pub timeout_at: BlockNumber,
+	}
+
+	/// Recent tosses of an account, used for rate limiting
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	pub struct TossActivity<BlockNumber> {
+		pub last_toss: BlockNumber,
+		/// Period of the last toss
+		pub period: u32,
+		/// Number of tosses in that period
+		pub tosses: u32,
+	}
+
+	/// Outcome of a lottery draw
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	pub struct LotteryResult<AccountId, Balance> {
+		pub winner: AccountId,
+		/// Number of tickets sold for the draw
+		pub tickets: u32,
+		/// Pot paid to the winner, including the pots rolled over from previous draws
+		pub prize: Balance,
+		/// Part of the pot kept by the house
+		pub house_cut: Balance,
+	}
+
+	/// Stakes accepted on wagers in an asset
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	pub struct BetLimits<Balance> {
+		pub min_bet: Balance,
+		pub max_bet: Balance,
+		/// Value of one unit of the asset in the native currency, which stakes and payouts in
+		/// the asset count for against the play limits
+		pub native_rate: FixedU128,
+	}
+
+	/// Shares of the house locked until the withdrawal delay passes and they are redeemed
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	pub struct LiquidityWithdrawal<Balance, BlockNumber> {
+		pub shares: Balance,
+		/// Value of the shares when the withdrawal was requested, the most they redeem for
+		pub value: Balance,
+		pub unlock_at: BlockNumber,
+		/// First block at which the shares can no longer be redeemed
+		pub expires_at: BlockNumber,
+	}
+
+	/// Limits a player sets on their own wagering. `None` means no limit.
+	#[derive(Clone, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	pub struct PlayLimits<Balance> {
+		pub daily_loss_cap: Option<Balance>,
+		pub weekly_loss_cap: Option<Balance>,
+		pub stake_cap: Option<Balance>,
+	}
+
+	impl<Balance: Ord + Copy> PlayLimits<Balance> {
+		/// The strictest of both limits, field by field.
+		pub fn strictest(&self, other: &Self) -> Self {
+			let min = |a: Option<Balance>, b: Option<Balance>| match (a, b) {
+				(Some(a), Some(b)) => Some(a.min(b)),
+				(a, None) => a,
+				(None, b) => b,
+			};
+			Self {
+				daily_loss_cap: min(self.daily_loss_cap, other.daily_loss_cap),
+				weekly_loss_cap: min(self.weekly_loss_cap, other.weekly_loss_cap),
+				stake_cap: min(self.stake_cap, other.stake_cap),
+			}
+		}
+	}
+
+	/// Looser play limits waiting for the loosening delay to pass
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	pub struct PendingPlayLimits<Balance, BlockNumber> {
+		pub limits: PlayLimits<Balance>,
+		pub effective_at: BlockNumber,
+	}
+
+	/// Payouts of at least `threshold` vest linearly over `duration` blocks once claimed
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	pub struct PayoutVesting<Balance, BlockNumber> {
+		pub threshold: Balance,
+		pub duration: BlockNumber,
+	}
+
+	/// Net losses of a player in the current day and week
+	#[derive(Clone, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	pub struct PlayerLosses<Balance> {
+		pub day: u32,
+		pub daily: Balance,
+		pub week: u32,
+		pub weekly: Balance,
 	}
 
-	const COIN_STORAGE_LIMIT: u32 = 10;
+	/// A jackpot won by a player
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	pub struct JackpotWin<AccountId, Balance, BlockNumber> {
+		pub winner: AccountId,
+		pub amount: Balance,
+		pub block: BlockNumber,
+	}
+
+	/// A match of a tournament bracket. Without a second player the first one advances on a bye.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	pub struct Match<AccountId> {
+		pub first: AccountId,
+		pub second: Option<AccountId>,
+		pub winner: AccountId,
+	}
+
+	pub(crate) const COIN_STORAGE_LIMIT: u32 = 10;
+
+	/// Number of sides of a coin
+	pub const COIN_SIDES: u32 = 2;
+
+	/// Times a random hash is rehashed before giving up on rejection sampling
+	const MAX_REHASHES: u32 = 8;
+
+	/// Milliseconds the off-chain worker waits for the beacon to answer
+	const BEACON_HTTP_TIMEOUT_MS: u64 = 2_000;
+
+	/// Blocks the off-chain worker waits before submitting the same beacon round again
+	const BEACON_RESUBMIT_INTERVAL: u32 = 5;
+
+	/// Picoseconds spent decoding a lottery ticket when the winner is drawn
+	const LOTTERY_TICKET_REF_TIME: u64 = 1_000_000;
 
 	#[pallet::storage]
 	#[pallet::getter(fn something)]
 	pub type CoinStorage<T> = StorageValue<_, BoundedBTreeMap<AccountIdOf<T>, Coin, ConstU32<COIN_STORAGE_LIMIT>>, OptionQuery>;
 
+	/// Funds held by the house on behalf of players: pool game stakes, pending streak winnings,
+	/// the payouts of open series and claimable winnings
+	#[pallet::storage]
+	pub type Committed<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+	/// Whether house-backed wagering is paused by the circuit breaker or the admin
+	#[pallet::storage]
+	pub type WageringPaused<T> = StorageValue<_, bool, ValueQuery>;
+
+	/// Shares of the house owned by every liquidity provider. The shares funded by the admin or
+	/// by `fund_house` are owned by the house account itself.
+	#[pallet::storage]
+	pub type LiquidityShares<T: Config> =
+		StorageMap<_, Blake2_128Concat, AccountIdOf<T>, BalanceOf<T>, ValueQuery>;
+
+	/// Total shares of the house
+	#[pallet::storage]
+	pub type TotalShares<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+	/// Pending liquidity withdrawal of every provider
+	#[pallet::storage]
+	pub type LiquidityWithdrawals<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		AccountIdOf<T>,
+		LiquidityWithdrawal<BalanceOf<T>, BlockNumberFor<T>>,
+		OptionQuery,
+	>;
+
+	/// Bet limits of every asset accepted for wagers. The house bankroll of an asset is the
+	/// balance of the house account in that asset.
+	#[pallet::storage]
+	pub type AssetBetLimits<T: Config> =
+		StorageMap<_, Blake2_128Concat, AssetIdOf<T>, BetLimits<AssetBalanceOf<T>>, OptionQuery>;
+
+	/// Stakes held in escrow for pending wagers, by wager and player
+	#[pallet::storage]
+	pub type Escrows<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		EscrowPurpose,
+		Blake2_128Concat,
+		AccountIdOf<T>,
+		Escrow<BalanceOf<T>, BlockNumberFor<T>>,
+		OptionQuery,
+	>;
+
+	/// Escrows refunded at a given block if their wager is still unresolved
+	#[pallet::storage]
+	pub type EscrowTimeouts<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		BlockNumberFor<T>,
+		BoundedVec<(EscrowPurpose, AccountIdOf<T>), T::MaxEscrowTimeouts>,
+		ValueQuery,
+	>;
+
+	/// Block of the last toss, or of the creation, of every coin
+	#[pallet::storage]
+	pub type CoinLastActivity<T: Config> =
+		StorageMap<_, Blake2_128Concat, AccountIdOf<T>, BlockNumberFor<T>, OptionQuery>;
+
+	/// Highest beacon round imported so far
+	#[pallet::storage]
+	pub type LatestBeaconRound<T> = StorageValue<_, u64, ValueQuery>;
+
+	/// Wagers waiting for the randomness of a beacon round
+	#[pallet::storage]
+	pub type BeaconWagers<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		u64,
+		BoundedVec<BeaconWager<T>, T::MaxBeaconWagers>,
+		ValueQuery,
+	>;
+
+	/// Double-or-nothing toss of every account waiting for the beacon
+	#[pallet::storage]
+	pub type StreakTosses<T: Config> =
+		StorageMap<_, Blake2_128Concat, AccountIdOf<T>, TossId, OptionQuery>;
+
+	/// Series toss of every account waiting for the beacon
+	#[pallet::storage]
+	pub type SeriesTosses<T: Config> =
+		StorageMap<_, Blake2_128Concat, AccountIdOf<T>, TossId, OptionQuery>;
+
+	/// Funds of the house bankroll of every asset owed to wagers waiting for the beacon
+	#[pallet::storage]
+	pub type AssetCommitted<T: Config> =
+		StorageMap<_, Blake2_128Concat, AssetIdOf<T>, AssetBalanceOf<T>, ValueQuery>;
+
+	/// Best-of-N series being played by each account
+	#[pallet::storage]
+	pub type OpenSeries<T: Config> =
+		StorageMap<_, Blake2_128Concat, AccountIdOf<T>, Series<BalanceOf<T>>, OptionQuery>;
+
+	/// Double-or-nothing streak of each account
+	#[pallet::storage]
+	pub type Streaks<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		AccountIdOf<T>,
+		Streak<BalanceOf<T>, BlockNumberFor<T>>,
+		OptionQuery,
+	>;
+
+	/// Accounts whose streak expires at a given block
+	#[pallet::storage]
+	pub type StreakExpiries<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		BlockNumberFor<T>,
+		BoundedVec<AccountIdOf<T>, T::MaxExpiringStreaks>,
+		ValueQuery,
+	>;
+
+	/// Guesses and stakes of the players of each global round
+	#[pallet::storage]
+	pub type RoundEntries<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		u32,
+		BoundedVec<(AccountIdOf<T>, CoinSide, BalanceOf<T>), T::MaxRoundEntries>,
+		ValueQuery,
+	>;
+
+	/// Beacon round resolving each global round, fixed when the round opens
+	#[pallet::storage]
+	pub type RoundBeaconRounds<T: Config> = StorageMap<_, Twox64Concat, u32, u64, OptionQuery>;
+
+	/// Outcome of every resolved global round
+	#[pallet::storage]
+	pub type RoundResults<T: Config> =
+		StorageMap<_, Twox64Concat, u32, RoundResult<BalanceOf<T>>, OptionQuery>;
+
+	/// Tournaments by id
+	#[pallet::storage]
+	pub type Tournaments<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		TournamentId,
+		Tournament<AccountIdOf<T>, BalanceOf<T>, BlockNumberFor<T>>,
+		OptionQuery,
+	>;
+
+	/// Registered players of a tournament, and once it started the players still in the bracket
+	/// in bracket order
+	#[pallet::storage]
+	pub type TournamentPlayers<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		TournamentId,
+		BoundedVec<AccountIdOf<T>, T::MaxTournamentPlayers>,
+		ValueQuery,
+	>;
+
+	/// Matches played in each round of a tournament bracket
+	#[pallet::storage]
+	pub type TournamentMatches<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		TournamentId,
+		Twox64Concat,
+		u32,
+		BoundedVec<Match<AccountIdOf<T>>, T::MaxTournamentPlayers>,
+		ValueQuery,
+	>;
+
+	/// Tournaments open for registration or in progress
+	#[pallet::storage]
+	pub type ActiveTournaments<T: Config> =
+		StorageValue<_, BoundedVec<TournamentId, T::MaxActiveTournaments>, ValueQuery>;
+
+	#[pallet::storage]
+	pub type NextTournamentId<T> = StorageValue<_, TournamentId, ValueQuery>;
+
+	/// Recent tosses of every account
+	#[pallet::storage]
+	pub type TossActivities<T: Config> =
+		StorageMap<_, Blake2_128Concat, AccountIdOf<T>, TossActivity<BlockNumberFor<T>>, OptionQuery>;
+
+	/// Free toss period and free tosses used in it by every account
+	#[pallet::storage]
+	pub type FreeTossesUsed<T: Config> =
+		StorageMap<_, Blake2_128Concat, AccountIdOf<T>, (u32, u32), ValueQuery>;
+
+	/// Id of the next coin toss, also giving each toss its own randomness subject
+	#[pallet::storage]
+	pub type NextTossId<T> = StorageValue<_, TossId, ValueQuery>;
+
+	/// Ticket holders of each lottery draw, one entry per ticket
+	#[pallet::storage]
+	pub type LotteryTickets<T: Config> =
+		StorageMap<_, Twox64Concat, u32, BoundedVec<AccountIdOf<T>, T::MaxTickets>, ValueQuery>;
+
+	/// Ticket sales not paid out yet, rolled over to the next draw when nobody wins
+	#[pallet::storage]
+	pub type LotteryPot<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+	/// Outcome of every lottery draw with a winner
+	#[pallet::storage]
+	pub type LotteryResults<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		u32,
+		LotteryResult<AccountIdOf<T>, BalanceOf<T>>,
+		OptionQuery,
+	>;
+
+	/// Play limits set by every player
+	#[pallet::storage]
+	pub type PlayerLimits<T: Config> =
+		StorageMap<_, Blake2_128Concat, AccountIdOf<T>, PlayLimits<BalanceOf<T>>, ValueQuery>;
+
+	/// Looser play limits requested by every player, not in effect yet
+	#[pallet::storage]
+	pub type PendingPlayerLimits<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		AccountIdOf<T>,
+		PendingPlayLimits<BalanceOf<T>, BlockNumberFor<T>>,
+		OptionQuery,
+	>;
+
+	/// Block until which every self-excluded player cannot wager
+	#[pallet::storage]
+	pub type SelfExclusions<T: Config> =
+		StorageMap<_, Blake2_128Concat, AccountIdOf<T>, BlockNumberFor<T>, OptionQuery>;
+
+	/// Net losses of every player, counted against their loss caps
+	#[pallet::storage]
+	pub type Losses<T: Config> =
+		StorageMap<_, Blake2_128Concat, AccountIdOf<T>, PlayerLosses<BalanceOf<T>>, ValueQuery>;
+
+	/// Winnings credited to every player, held by the house until claimed
+	#[pallet::storage]
+	pub type Claimable<T: Config> =
+		StorageMap<_, Blake2_128Concat, AccountIdOf<T>, BalanceOf<T>, ValueQuery>;
+
+	/// Vesting of large payouts set by the admin, if any
+	#[pallet::storage]
+	pub type PayoutVestingRule<T: Config> =
+		StorageValue<_, PayoutVesting<BalanceOf<T>, BlockNumberFor<T>>, OptionQuery>;
+
+	/// Part of the claimable balance of every player that vests once claimed
+	#[pallet::storage]
+	pub type VestingClaimable<T: Config> =
+		StorageMap<_, Blake2_128Concat, AccountIdOf<T>, BalanceOf<T>, ValueQuery>;
+
+	/// Funds of the jackpot, held by the house
+	#[pallet::storage]
+	pub type JackpotPot<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+	/// Consecutive staked coin tosses guessed right by every account
+	#[pallet::storage]
+	pub type JackpotStreaks<T: Config> =
+		StorageMap<_, Blake2_128Concat, AccountIdOf<T>, u32, ValueQuery>;
+
+	/// Number of jackpots won so far
+	#[pallet::storage]
+	pub type JackpotWinCount<T> = StorageValue<_, u32, ValueQuery>;
+
+	/// Every jackpot won, by order of winning
+	#[pallet::storage]
+	pub type JackpotWins<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		u32,
+		JackpotWin<AccountIdOf<T>, BalanceOf<T>, BlockNumberFor<T>>,
+		OptionQuery,
+	>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
 		/// A new coin has been created
-		CoinCreated(AccountIdOf<T>),
+		CoinCreated { who: AccountIdOf<T> },
 		/// Coin has been Flipped
-		CoinFlipped(AccountIdOf<T>, CoinSide),
+		CoinFlipped { who: AccountIdOf<T>, side: CoinSide },
 		/// Coin side guessed
-		CoinGuessed(AccountIdOf<T>, CoinSide),
+		CoinGuessed {
+			toss_id: TossId,
+			who: AccountIdOf<T>,
+			guess: CoinSide,
+			result: CoinSide,
+			stake: BalanceOf<T>,
+			/// Amount won by the player
+			payout: BalanceOf<T>,
+			randomness: RandomnessSource<BlockNumberFor<T>>,
+		},
 		/// Coin side not guessed
-		CoinNotGuessed(AccountIdOf<T>, CoinSide),
+		CoinNotGuessed {
+			toss_id: TossId,
+			who: AccountIdOf<T>,
+			guess: CoinSide,
+			result: CoinSide,
+			/// Stake lost by the player
+			stake: BalanceOf<T>,
+			randomness: RandomnessSource<BlockNumberFor<T>>,
+		},
+		/// Coin tossed with a stake in an asset, with the amount paid out when the guess is right
+		AssetCoinTossed {
+			toss_id: TossId,
+			who: AccountIdOf<T>,
+			asset: AssetIdOf<T>,
+			guess: CoinSide,
+			result: CoinSide,
+			stake: AssetBalanceOf<T>,
+			payout: AssetBalanceOf<T>,
+			randomness: RandomnessSource<BlockNumberFor<T>>,
+		},
 		/// Coin has been removed
-		CoinRemoved(AccountIdOf<T>),
+		CoinRemoved { who: AccountIdOf<T> },
+		/// Coin left idle for `CoinExpiry` blocks has been removed and its deposit returned
+		CoinExpired { who: AccountIdOf<T>, last_activity: BlockNumberFor<T>, deposit: BalanceOf<T> },
+		/// Wager waiting for the randomness of the given beacon round
+		WagerQueued { toss_id: TossId, who: AccountIdOf<T>, round: u64 },
+		/// Wager refunded without being resolved: its stake was given back and its payout
+		/// released
+		WagerRefunded { toss_id: TossId, who: AccountIdOf<T>, round: u64 },
+		/// Beacon round has been imported and the wagers waiting on it resolved
+		BeaconPulseImported { round: u64, randomness: [u8; 32] },
+		/// Dice rolled, with the amount paid out when the guess is right
+		DiceRolled {
+			toss_id: TossId,
+			who: AccountIdOf<T>,
+			sides: u32,
+			guess: u32,
+			result: u32,
+			stake: BalanceOf<T>,
+			payout: BalanceOf<T>,
+			randomness: RandomnessSource<BlockNumberFor<T>>,
+		},
+		/// Best-of-N series opened
+		SeriesOpened { who: AccountIdOf<T>, guess: CoinSide, length: u32, stake: BalanceOf<T> },
+		/// Series toss recorded, with the heads and tails so far
+		SeriesTossed {
+			toss_id: TossId,
+			who: AccountIdOf<T>,
+			result: CoinSide,
+			heads: u32,
+			tails: u32,
+			randomness: RandomnessSource<BlockNumberFor<T>>,
+		},
+		/// Series won by the player
+		SeriesWon { who: AccountIdOf<T>, payout: BalanceOf<T> },
+		/// Series lost by the player, with the stake kept by the house
+		SeriesLost { who: AccountIdOf<T>, stake: BalanceOf<T> },
+		/// Series left open past `SeriesExpiry`, with the stake refunded
+		SeriesExpired { who: AccountIdOf<T>, stake: BalanceOf<T> },
+		/// Double-or-nothing toss won
+		StreakWon { who: AccountIdOf<T>, pending: BalanceOf<T>, length: u32 },
+		/// Pending winnings of a streak paid out to the player
+		StreakSettled { who: AccountIdOf<T>, amount: BalanceOf<T> },
+		/// Player joined a global round
+		RoundJoined { who: AccountIdOf<T>, round: u32, guess: CoinSide, stake: BalanceOf<T> },
+		/// Global round resolved
+		RoundResolved {
+			toss_id: TossId,
+			round: u32,
+			result: RoundResult<BalanceOf<T>>,
+			randomness: RandomnessSource<BlockNumberFor<T>>,
+		},
+		/// Stake and share of the losers' pool paid to a player of a global round
+		RoundPaidOut { who: AccountIdOf<T>, round: u32, amount: BalanceOf<T> },
+		/// Tournament created by the organizer
+		TournamentCreated { id: TournamentId, organizer: AccountIdOf<T> },
+		/// Player registered for a tournament
+		TournamentJoined { id: TournamentId, who: AccountIdOf<T> },
+		/// Bracket of a tournament generated
+		TournamentStarted { id: TournamentId, players: u32 },
+		/// Bracket round of a tournament played
+		TournamentRoundPlayed {
+			id: TournamentId,
+			round: u32,
+			randomness: RandomnessSource<BlockNumberFor<T>>,
+		},
+		/// Tournament finished and prizes paid to the top finishers
+		TournamentFinished {
+			id: TournamentId,
+			champion: AccountIdOf<T>,
+			champion_prize: BalanceOf<T>,
+			runner_up: AccountIdOf<T>,
+			runner_up_prize: BalanceOf<T>,
+		},
+		/// Tournament cancelled by the organizer or for lack of players, entry fees and deposit
+		/// released
+		TournamentCancelled { id: TournamentId },
+		/// Batch of tosses resolved. The tosses have consecutive ids starting at `first_toss_id`.
+		TossesBatched {
+			who: AccountIdOf<T>,
+			first_toss_id: TossId,
+			tosses: u32,
+			won: u32,
+			staked: BalanceOf<T>,
+			paid_out: BalanceOf<T>,
+		},
+		/// Lottery tickets bought
+		TicketsBought { who: AccountIdOf<T>, draw: u32, count: u32 },
+		/// Lottery draw resolved
+		LotteryDrawn {
+			draw: u32,
+			result: LotteryResult<AccountIdOf<T>, BalanceOf<T>>,
+			randomness: RandomnessSource<BlockNumberFor<T>>,
+		},
+		/// Lottery pot carried over to the next draw
+		LotteryRolledOver { draw: u32, pot: BalanceOf<T> },
+		/// Winnings credited to the claimable balance of a player
+		WinningsCredited { who: AccountIdOf<T>, amount: BalanceOf<T> },
+		/// Winnings withdrawn by a player, with the claimable balance left
+		WinningsClaimed { who: AccountIdOf<T>, amount: BalanceOf<T>, remaining: BalanceOf<T> },
+		/// Claimed winnings locked in a vesting schedule releasing `per_block` every block
+		WinningsVested { who: AccountIdOf<T>, amount: BalanceOf<T>, per_block: BalanceOf<T> },
+		/// Vesting of large payouts set by the admin, or removed without a rule
+		PayoutVestingSet { rule: Option<PayoutVesting<BalanceOf<T>, BlockNumberFor<T>>> },
+		/// Jackpot won by guessing `JackpotStreak` staked coin tosses in a row
+		JackpotWon { who: AccountIdOf<T>, index: u32, amount: BalanceOf<T> },
+		/// Play limits of a player in effect
+		PlayLimitsSet { who: AccountIdOf<T>, limits: PlayLimits<BalanceOf<T>> },
+		/// Looser play limits of a player taking effect at `effective_at`
+		PlayLimitsScheduled {
+			who: AccountIdOf<T>,
+			limits: PlayLimits<BalanceOf<T>>,
+			effective_at: BlockNumberFor<T>,
+		},
+		/// Player excluded from wagering until the given block
+		SelfExcluded { who: AccountIdOf<T>, until: BlockNumberFor<T> },
+		/// Stake held in escrow until its wager is resolved, or refunded at `timeout_at`
+		EscrowHeld {
+			purpose: EscrowPurpose,
+			who: AccountIdOf<T>,
+			amount: BalanceOf<T>,
+			timeout_at: BlockNumberFor<T>,
+		},
+		/// Escrowed stake moved to the house on resolution of its wager
+		EscrowSettled { purpose: EscrowPurpose, who: AccountIdOf<T>, amount: BalanceOf<T> },
+		/// Escrowed stake given back to the player, on cancellation of its wager or after its
+		/// timeout
+		EscrowReleased {
+			purpose: EscrowPurpose,
+			who: AccountIdOf<T>,
+			amount: BalanceOf<T>,
+			timed_out: bool,
+		},
+		/// Funds added to the house
+		HouseFunded { who: AccountIdOf<T>, amount: BalanceOf<T> },
+		/// Funds withdrawn from the house by the admin
+		HouseWithdrawn { dest: AccountIdOf<T>, amount: BalanceOf<T> },
+		/// Liquidity added to the house in exchange for shares
+		LiquidityProvided { who: AccountIdOf<T>, amount: BalanceOf<T>, shares: BalanceOf<T> },
+		/// Shares of the house locked for redemption between `unlock_at` and `expires_at`, for
+		/// at most `value`
+		LiquidityWithdrawalRequested {
+			who: AccountIdOf<T>,
+			shares: BalanceOf<T>,
+			value: BalanceOf<T>,
+			unlock_at: BlockNumberFor<T>,
+			expires_at: BlockNumberFor<T>,
+		},
+		/// Shares of the house redeemed
+		LiquidityRedeemed { who: AccountIdOf<T>, shares: BalanceOf<T>, amount: BalanceOf<T> },
+		/// Shares of an expired withdrawal request given back to their owner
+		LiquidityWithdrawalExpired { who: AccountIdOf<T>, shares: BalanceOf<T> },
+		/// Rake charged on top of the stake of a house-backed wager
+		RakeCollected { who: AccountIdOf<T>, amount: BalanceOf<T> },
+		/// Bet limits of an asset set by the admin. Wagers in the asset are refused without limits.
+		AssetBetLimitsSet { asset: AssetIdOf<T>, limits: Option<BetLimits<AssetBalanceOf<T>>> },
+		/// Funds added to the house bankroll of an asset
+		AssetHouseFunded { who: AccountIdOf<T>, asset: AssetIdOf<T>, amount: AssetBalanceOf<T> },
+		/// Funds withdrawn from the house bankroll of an asset by the admin
+		AssetHouseWithdrawn { asset: AssetIdOf<T>, dest: AccountIdOf<T>, amount: AssetBalanceOf<T> },
+		/// House-backed wagering paused, with the bankroll available at that time
+		WageringPaused { available: BalanceOf<T> },
+		/// House-backed wagering resumed by the admin
+		WageringResumed,
 	}
 
 	#[derive(PartialEq)]
@@ -84,64 +1173,1019 @@ pub mod pallet {
 		CoinAlreadyExists,
 		/// Coin not found
 		CoinNotFound,
+		/// Every coin slot is taken
+		TooManyCoins,
+		/// House-backed wagering is paused
+		WageringPaused,
+		/// Stake above the share of the bankroll allowed for a single wager
+		StakeTooHigh,
+		/// No wager waits on the beacon round, or it was already imported
+		BeaconRoundNotAwaited,
+		/// Beacon pulse failed verification
+		InvalidBeaconPulse,
+		/// Too many wagers, or tosses, waiting on the same beacon round
+		TooManyBeaconWagers,
+		/// Number of sides out of the allowed range
+		InvalidSides,
+		/// Guess is not one of the sides of the dice
+		InvalidGuess,
+		/// House cannot cover the payout of the wager
+		InsufficientHouseFunds,
+		/// Coin bias must leave a chance to both sides
+		InvalidBias,
+		/// Series length must be odd and not above the maximum
+		InvalidSeriesLength,
+		/// A series is already being played
+		SeriesAlreadyOpen,
+		/// No series is being played
+		SeriesNotFound,
+		/// Stake must not be zero
+		InvalidStake,
+		/// Pending winnings must be rolled or cashed out before starting a new streak
+		StreakAlreadyOpen,
+		/// No pending winnings to roll or cash out
+		NoPendingWinnings,
+		/// Too many streaks expiring in the same block
+		TooManyExpiringStreaks,
+		/// Player already joined the current global round
+		AlreadyInRound,
+		/// Global round has no room for more players
+		RoundFull,
+		/// Tournament needs between two and `MaxTournamentPlayers` players and a future start
+		InvalidTournament,
+		/// Tournament not found
+		TournamentNotFound,
+		/// Tournament no longer accepts registrations
+		RegistrationClosed,
+		/// Tournament has no room for more players
+		TournamentFull,
+		/// Player already registered for the tournament
+		AlreadyRegistered,
+		/// Too many tournaments open or in progress
+		TooManyTournaments,
+		/// Cooldown not elapsed or too many tosses in the current period
+		TooSoon,
+		/// At least one guess must be given
+		EmptyBatch,
+		/// At least one ticket must be bought
+		InvalidTicketCount,
+		/// Draw has no tickets left for sale
+		TooManyTickets,
+		/// Player excluded from wagering
+		SelfExcluded,
+		/// Stake above the stake cap of the player
+		StakeCapExceeded,
+		/// Stake would take the losses of the day above the daily loss cap of the player
+		DailyLossCapReached,
+		/// Stake would take the losses of the week above the weekly loss cap of the player
+		WeeklyLossCapReached,
+		/// No winnings to claim
+		NothingToClaim,
+		/// Amount above the claimable balance
+		InsufficientClaimable,
+		/// Vesting threshold and duration must not be zero
+		InvalidPayoutVesting,
+		/// Asset not accepted for wagers
+		AssetNotAccepted,
+		/// Stake outside the bet limits of the asset
+		StakeOutOfLimits,
+		/// Minimum bet above the maximum bet
+		InvalidBetLimits,
+		/// Amount too small to be worth a share, or shares worth nothing
+		TooLittleLiquidity,
+		/// Not enough shares of the house
+		InsufficientShares,
+		/// No liquidity withdrawal requested
+		WithdrawalNotFound,
+		/// Withdrawal delay not elapsed
+		WithdrawalLocked,
+		/// Too many escrows timing out in the same block
+		TooManyEscrowTimeouts,
+		/// No room for another vesting schedule of the claimed winnings
+		TooManyVestingSchedules,
+		/// Double-or-nothing toss still waiting for the beacon
+		StreakTossPending,
+		/// Series toss still waiting for the beacon
+		SeriesTossPending,
+		/// Beacon round resolving the global round already published
+		RoundClosed,
+		/// Escrowed stake of a wager could not be moved to the house
+		EscrowNotSettled,
+		/// Only the organizer can cancel a tournament
+		NotTournamentOrganizer,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+			Self::settle_expired_streaks(n)
+				.saturating_add(Self::advance_tournaments(n))
+				.saturating_add(Self::draw_lottery(n))
+				.saturating_add(Self::refund_timed_out_escrows(n))
+		}
+
+		fn on_idle(n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			Self::reap_expired_coins(n, remaining_weight)
+		}
+
+		fn offchain_worker(block_number: BlockNumberFor<T>) {
+			if let Err(e) = Self::fetch_and_submit_beacon_pulse(block_number) {
+				log::warn!(target: LOG_TARGET, "Beacon pulse not imported: {}", e);
+			}
+		}
 	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		#[pallet::call_index(0)]
-		#[pallet::weight(T::WeightInfo::default_weight())]
-		pub fn create_coin(origin: OriginFor<T>) -> DispatchResult {
+		#[pallet::weight(T::WeightInfo::create_coin())]
+		pub fn create_coin(
+			origin: OriginFor<T>,
+			head_probability: Option<Permill>,
+		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
-			Self::do_create_coin(&who)?;
-			Self::deposit_event(Event::CoinCreated(who));
+			Self::do_create_coin(&who, head_probability)?;
+			Self::deposit_event(Event::CoinCreated { who });
 			Ok(())
 		}
 
+		/// Toss the coin, staking `stake` on `coin_side`. The toss is resolved against a future
+		/// beacon round. Successful tosses within the free toss quota of the account don't pay
+		/// the transaction fee.
 		#[pallet::call_index(1)]
-		#[pallet::weight(T::WeightInfo::default_weight())]
-		pub fn toss_coin(origin: OriginFor<T>, coin_side: CoinSide) -> DispatchResult {
+		#[pallet::weight(T::WeightInfo::toss_coin())]
+		pub fn toss_coin(
+			origin: OriginFor<T>,
+			coin_side: CoinSide,
+			stake: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
 
 			let coin = Self::get_coin(&who)?;
+			Self::note_tosses(&who, 1)?;
 
-			let toss_result = Self::random_coin_side();
+			let payout = Self::coin_payout_for(stake, coin.head_probability, &coin_side);
+			Self::place_wager(&who, stake, payout)?;
+			let pays_fee = Self::use_free_toss(&who);
+			Self::queue_wager(
+				Self::next_toss_id(),
+				&who,
+				WagerKind::Toss {
+					guess: coin_side,
+					head_probability: coin.head_probability,
+					stake,
+					payout,
+				},
+			)?;
 
-			if coin_side == toss_result {
-				Self::deposit_event(Event::CoinGuessed(who.clone(), toss_result.clone()));
-			} else {
-				Self::deposit_event(Event::CoinNotGuessed(who.clone(), toss_result.clone()));
-			}
+			Ok(pays_fee.into())
+		}
 
-			if coin.side != toss_result.clone() {
-				Self::mutate_coin(&who, Coin {
-					side: toss_result.clone(),
-				});
-				Self::deposit_event(Event::CoinFlipped(who, toss_result));
-			}
+		/// Toss the coin once for every guess, staking `stake` on each of them. The tosses are
+		/// resolved against a future beacon round, each with its own toss id, and only a
+		/// summary of the batch is emitted.
+		#[pallet::call_index(15)]
+		#[pallet::weight(T::WeightInfo::toss_many(guesses.len() as u32))]
+		pub fn toss_many(
+			origin: OriginFor<T>,
+			guesses: BoundedVec<CoinSide, T::MaxBatch>,
+			stake: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
 
-			Ok(())
+			ensure!(!guesses.is_empty(), Error::<T>::EmptyBatch);
+			let coin = Self::get_coin(&who)?;
+			Self::note_tosses(&who, guesses.len() as u32)?;
+
+			let tosses = guesses.len() as u32;
+			let staked = stake.saturating_mul(tosses.into());
+			let max_payout = Self::batch_payout_for(stake, coin.head_probability, &guesses);
+			Self::place_wager(&who, staked, max_payout)?;
+
+			let first_toss_id = NextTossId::<T>::mutate(|id| {
+				let first_toss_id = *id;
+				*id = id.wrapping_add(tosses.into());
+				first_toss_id
+			});
+			Self::queue_wager(
+				first_toss_id,
+				&who,
+				WagerKind::Batch { guesses, head_probability: coin.head_probability, stake },
+			)
 		}
 
 		#[pallet::call_index(2)]
-		#[pallet::weight(T::WeightInfo::default_weight())]
+		#[pallet::weight(T::WeightInfo::remove_coin())]
 		pub fn remove_coin(origin: OriginFor<T>) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 			Self::do_remove_coin(&who)?;
-			Self::deposit_event(Event::CoinRemoved(who));
+			Self::deposit_event(Event::CoinRemoved { who });
 			Ok(())
 		}
-	}
 
-	impl<T: Config> Pallet<T> {
-		pub fn do_create_coin(who: &T::AccountId) -> DispatchResult {
-			let side = Self::random_coin_side();
-			let coin = Coin { side };
+		/// Toss the coin without a stake against the randomness of a future beacon round. The
+		/// toss is resolved once that round is imported by the off-chain worker.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::toss_coin_with_beacon())]
+		pub fn toss_coin_with_beacon(origin: OriginFor<T>, coin_side: CoinSide) -> DispatchResult {
+			let who = ensure_signed(origin)?;
 
-			if Self::get_coin(who).is_ok() {
-				return Err(Error::<T>::CoinAlreadyExists.into());
-			}
+			let coin = Self::get_coin(&who)?;
+			Self::note_tosses(&who, 1)?;
 
-			Self::insert_coin(who, coin);
+			Self::queue_wager(
+				Self::next_toss_id(),
+				&who,
+				WagerKind::Toss {
+					guess: coin_side,
+					head_probability: coin.head_probability,
+					stake: Zero::zero(),
+					payout: Zero::zero(),
+				},
+			)
+		}
+
+		/// Import a beacon round that wagers wait on and resolve them. The weight of the
+		/// verification and of the most tosses a round can resolve is charged, and the tosses
+		/// not resolved are refunded.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::submit_beacon_pulse(T::MaxBeaconTosses::get()))]
+		pub fn submit_beacon_pulse(
+			origin: OriginFor<T>,
+			pulse: BeaconPulse,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			Self::do_import_beacon_pulse(pulse)
+		}
+
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::submit_beacon_pulse(T::MaxBeaconTosses::get()))]
+		pub fn submit_beacon_pulse_unsigned(
+			origin: OriginFor<T>,
+			pulse: BeaconPulse,
+		) -> DispatchResultWithPostInfo {
+			ensure_none(origin)?;
+			Self::do_import_beacon_pulse(pulse)
+		}
+
+		/// Roll a dice with the given number of sides, guessing a face between 1 and `sides`.
+		/// A winning guess pays the stake times the number of sides. The roll is resolved
+		/// against a future beacon round.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::roll())]
+		pub fn roll(
+			origin: OriginFor<T>,
+			sides: u32,
+			guess: u32,
+			stake: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(sides >= COIN_SIDES && sides <= T::MaxSides::get(), Error::<T>::InvalidSides);
+			ensure!(guess >= 1 && guess <= sides, Error::<T>::InvalidGuess);
+			ensure!(!stake.is_zero(), Error::<T>::InvalidStake);
+			Self::note_tosses(&who, 1)?;
+
+			let payout = Self::payout_for(stake, sides);
+			Self::place_wager(&who, stake, payout)?;
+			Self::queue_wager(
+				Self::next_toss_id(),
+				&who,
+				WagerKind::Roll { sides, guess, stake, payout },
+			)
+		}
+
+		/// Open a best-of-N series of fair tosses on `guess`. The stake is held in escrow once,
+		/// and the series is won when `guess` reaches the majority of the `length` tosses. A
+		/// series still open `SeriesExpiry` blocks later is closed and its stake refunded.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::open_series())]
+		pub fn open_series(
+			origin: OriginFor<T>,
+			guess: CoinSide,
+			length: u32,
+			stake: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(
+				length % 2 == 1 && length <= T::MaxSeriesLength::get(),
+				Error::<T>::InvalidSeriesLength
+			);
+			ensure!(!OpenSeries::<T>::contains_key(&who), Error::<T>::SeriesAlreadyOpen);
+			ensure!(!stake.is_zero(), Error::<T>::InvalidStake);
+
+			let payout = Self::payout_for(stake, COIN_SIDES);
+			Self::place_wager(&who, stake, payout)?;
+			let timeout_at =
+				frame_system::Pallet::<T>::block_number().saturating_add(T::SeriesExpiry::get());
+			Self::schedule_escrow_timeout(timeout_at, &EscrowPurpose::Series, &who)?;
+			Self::hold_escrow(EscrowPurpose::Series, &who, stake, timeout_at)?;
+
+			OpenSeries::<T>::insert(
+				&who,
+				Series { guess: guess.clone(), length, stake, heads: 0, tails: 0 },
+			);
+
+			Self::deposit_event(Event::SeriesOpened { who, guess, length, stake });
+			Ok(())
+		}
+
+		/// Toss the next coin of the open series against a future beacon round. The series is
+		/// resolved once a side reaches the majority.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::toss_series())]
+		pub fn toss_series(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(OpenSeries::<T>::contains_key(&who), Error::<T>::SeriesNotFound);
+			ensure!(!SeriesTosses::<T>::contains_key(&who), Error::<T>::SeriesTossPending);
+			Self::note_tosses(&who, 1)?;
+
+			let toss_id = Self::next_toss_id();
+			SeriesTosses::<T>::insert(&who, toss_id);
+			Self::queue_wager(toss_id, &who, WagerKind::SeriesToss)
+		}
+
+		/// Toss the coin in double-or-nothing mode. With a stake a new streak is started,
+		/// without one the pending winnings of the streak are rolled into the toss. The toss is
+		/// resolved against a future beacon round. Winnings stay in the house until cashed out,
+		/// the streak reaches `MaxStreakLength` or it expires.
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::double_or_nothing(T::MaxExpiringStreaks::get()))]
+		pub fn double_or_nothing(
+			origin: OriginFor<T>,
+			coin_side: CoinSide,
+			stake: Option<BalanceOf<T>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let coin = Self::get_coin(&who)?;
+			ensure!(!StreakTosses::<T>::contains_key(&who), Error::<T>::StreakTossPending);
+			Self::note_coin_activity(&who);
+			let streak = Streaks::<T>::get(&who);
+
+			let stake = match (stake, &streak) {
+				(Some(stake), None) => {
+					ensure!(!stake.is_zero(), Error::<T>::InvalidStake);
+					stake
+				},
+				(None, Some(streak)) => streak.pending,
+				(Some(_), Some(_)) => return Err(Error::<T>::StreakAlreadyOpen.into()),
+				(None, None) => return Err(Error::<T>::NoPendingWinnings.into()),
+			};
+
+			let payout = Self::coin_payout_for(stake, coin.head_probability, &coin_side);
+			match &streak {
+				// Pending winnings already live in the house
+				Some(streak) => {
+					Self::ensure_can_back(streak.pending, payout)?;
+					// Pending winnings were never paid out, their stake is still counted as lost
+					Self::ensure_within_limits(&who, streak.pending, Zero::zero())?;
+					Self::unschedule_streak_expiry(&who, streak.expires_at);
+					Streaks::<T>::remove(&who);
+					Self::release(streak.pending);
+					Self::commit(payout);
+				},
+				None => Self::place_wager(&who, stake, payout)?,
+			}
+
+			let toss_id = Self::next_toss_id();
+			StreakTosses::<T>::insert(&who, toss_id);
+			Self::queue_wager(
+				toss_id,
+				&who,
+				WagerKind::DoubleOrNothing {
+					guess: coin_side,
+					head_probability: coin.head_probability,
+					stake,
+					payout,
+					length: streak.map_or(0, |streak| streak.length),
+				},
+			)
+		}
+
+		/// Join the current global round. Everyone is resolved by the same flip, against the beacon
+		/// round published `BeaconRoundDelay` rounds after the end of the global round, and the
+		/// players that guessed it split the losers' pool pro rata to their stake. Joins close at
+		/// the end of the global round, or once that beacon round is published if the clock
+		/// drifted from `ExpectedBlockTime`.
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::join_round(T::MaxRoundEntries::get()))]
+		pub fn join_round(
+			origin: OriginFor<T>,
+			guess: CoinSide,
+			stake: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(!stake.is_zero(), Error::<T>::InvalidStake);
+
+			let now = frame_system::Pallet::<T>::block_number();
+			let round = Self::round_at(now);
+			let length = T::RoundLength::get().max(One::one());
+			let resolves_at = (now / length).saturating_add(One::one()).saturating_mul(length);
+			match RoundBeaconRounds::<T>::get(round) {
+				// Nobody joins once the randomness resolving the round may be known
+				Some(beacon_round) => ensure!(
+					Self::current_beacon_round() < beacon_round &&
+						LatestBeaconRound::<T>::get() < beacon_round,
+					Error::<T>::RoundClosed
+				),
+				None => {
+					let beacon_round = Self::beacon_round_at(resolves_at)
+						.saturating_add(T::BeaconRoundDelay::get())
+						.max(Self::next_wager_round());
+					Self::queue_wager_on(
+						beacon_round,
+						Self::next_toss_id(),
+						&Self::account_id(),
+						WagerKind::GlobalRound { round },
+					)?;
+					RoundBeaconRounds::<T>::insert(round, beacon_round);
+				},
+			}
+			RoundEntries::<T>::try_mutate(round, |entries| {
+				ensure!(
+					!entries.iter().any(|(player, _, _)| *player == who),
+					Error::<T>::AlreadyInRound
+				);
+				entries
+					.try_push((who.clone(), guess.clone(), stake))
+					.map_err(|_| Error::<T>::RoundFull)
+			})?;
+
+			// The stake stays with the player until the round is resolved, and is refunded if the
+			// beacon round never comes. The pool is fully paid back to the players, so no payout
+			// has to be covered.
+			Self::hold_in_escrow(EscrowPurpose::Round(round), &who, stake, resolves_at)?;
+
+			Self::deposit_event(Event::RoundJoined { who, round, guess, stake });
+			Ok(())
+		}
+
+		/// Create a knockout tournament. Players register until `start_block`, at most
+		/// `MaxRegistrationPeriod` blocks away, then one bracket round is played per block until a
+		/// champion remains. `TournamentDeposit` is held from the organizer until the tournament
+		/// starts or is cancelled.
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::create_tournament())]
+		pub fn create_tournament(
+			origin: OriginFor<T>,
+			entry_fee: BalanceOf<T>,
+			max_players: u32,
+			start_block: BlockNumberFor<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(
+				max_players >= 2 &&
+					max_players <= T::MaxTournamentPlayers::get() &&
+					start_block > now &&
+					start_block <= now.saturating_add(T::MaxRegistrationPeriod::get()),
+				Error::<T>::InvalidTournament
+			);
+
+			let id = NextTournamentId::<T>::get();
+			ActiveTournaments::<T>::try_append(id).map_err(|_| Error::<T>::TooManyTournaments)?;
+			NextTournamentId::<T>::put(id.saturating_add(1));
+
+			let deposit = T::TournamentDeposit::get();
+			T::NativeBalance::hold(&HoldReason::TournamentDeposit.into(), &who, deposit)?;
+
+			Tournaments::<T>::insert(
+				id,
+				Tournament {
+					organizer: who.clone(),
+					entry_fee,
+					max_players,
+					start_block,
+					deposit,
+					status: TournamentStatus::Registration,
+					prize_pool: Zero::zero(),
+				},
+			);
+
+			Self::deposit_event(Event::TournamentCreated { id, organizer: who });
+			Ok(())
+		}
+
+		/// Register for a tournament. Its entry fee is held in escrow until the tournament starts.
+		#[pallet::call_index(13)]
+		#[pallet::weight(T::WeightInfo::register_for_tournament(T::MaxTournamentPlayers::get()))]
+		pub fn register_for_tournament(origin: OriginFor<T>, id: TournamentId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut tournament = Tournaments::<T>::get(id).ok_or(Error::<T>::TournamentNotFound)?;
+			ensure!(
+				tournament.status == TournamentStatus::Registration &&
+					frame_system::Pallet::<T>::block_number() < tournament.start_block,
+				Error::<T>::RegistrationClosed
+			);
+
+			TournamentPlayers::<T>::try_mutate(id, |players| {
+				ensure!(!players.contains(&who), Error::<T>::AlreadyRegistered);
+				ensure!((players.len() as u32) < tournament.max_players, Error::<T>::TournamentFull);
+				players.try_push(who.clone()).map_err(|_| Error::<T>::TournamentFull)
+			})?;
+
+			Self::hold_in_escrow(
+				EscrowPurpose::Tournament(id),
+				&who,
+				tournament.entry_fee,
+				tournament.start_block,
+			)?;
+			tournament.prize_pool = tournament.prize_pool.saturating_add(tournament.entry_fee);
+			Tournaments::<T>::insert(id, tournament);
+
+			Self::deposit_event(Event::TournamentJoined { id, who });
+			Ok(())
+		}
+
+		/// Cancel a tournament still open for registration, releasing the entry fees of its
+		/// players and the deposit. Only the organizer can cancel it.
+		#[pallet::call_index(30)]
+		#[pallet::weight(T::WeightInfo::cancel_tournament(T::MaxTournamentPlayers::get()))]
+		pub fn cancel_tournament(origin: OriginFor<T>, id: TournamentId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut tournament = Tournaments::<T>::get(id).ok_or(Error::<T>::TournamentNotFound)?;
+			ensure!(tournament.organizer == who, Error::<T>::NotTournamentOrganizer);
+			ensure!(
+				tournament.status == TournamentStatus::Registration,
+				Error::<T>::RegistrationClosed
+			);
+
+			Self::cancel_registration(id, &mut tournament);
+			Tournaments::<T>::insert(id, tournament);
+			ActiveTournaments::<T>::mutate(|active| active.retain(|active_id| *active_id != id));
+			Ok(())
+		}
+
+		/// Buy `count` tickets for the current lottery draw.
+		#[pallet::call_index(14)]
+		#[pallet::weight(T::WeightInfo::buy_tickets((*count).min(T::MaxTickets::get())))]
+		pub fn buy_tickets(origin: OriginFor<T>, count: u32) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(count > 0, Error::<T>::InvalidTicketCount);
+
+			let draw = Self::lottery_draw_at(frame_system::Pallet::<T>::block_number());
+			LotteryTickets::<T>::try_mutate(draw, |tickets| {
+				ensure!(
+					tickets.len().saturating_add(count as usize) <= T::MaxTickets::get() as usize,
+					Error::<T>::TooManyTickets
+				);
+				for _ in 0..count {
+					tickets.try_push(who.clone()).map_err(|_| Error::<T>::TooManyTickets)?;
+				}
+				Ok::<_, DispatchError>(())
+			})?;
+
+			let cost = T::TicketPrice::get().saturating_mul(count.into());
+			Self::take_stake(&who, cost, Zero::zero())?;
+			Self::commit(cost);
+			LotteryPot::<T>::mutate(|pot| *pot = pot.saturating_add(cost));
+
+			Self::deposit_event(Event::TicketsBought { who, draw, count });
+			Ok(())
+		}
+
+		/// Add funds to the house bankroll. The shares minted for them are owned by the house.
+		#[pallet::call_index(16)]
+		#[pallet::weight(T::WeightInfo::fund_house())]
+		pub fn fund_house(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::mint_shares(&Self::account_id(), amount)?;
+			T::Currency::transfer(&who, &Self::account_id(), amount, ExistenceRequirement::KeepAlive)?;
+
+			Self::deposit_event(Event::HouseFunded { who, amount });
+			Ok(())
+		}
+
+		/// Withdraw funds from the house. Only the available bankroll, up to the value of the
+		/// shares owned by the house, can be withdrawn.
+		#[pallet::call_index(17)]
+		#[pallet::weight(T::WeightInfo::withdraw_from_house())]
+		pub fn withdraw_from_house(
+			origin: OriginFor<T>,
+			dest: T::AccountId,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			ensure!(amount <= Self::available_bankroll(), Error::<T>::InsufficientHouseFunds);
+			Self::burn_shares_for(&Self::account_id(), amount)?;
+			T::Currency::transfer(&Self::account_id(), &dest, amount, ExistenceRequirement::KeepAlive)?;
+
+			Self::deposit_event(Event::HouseWithdrawn { dest, amount });
+			Ok(())
+		}
+
+		/// Pause or resume house-backed wagering.
+		#[pallet::call_index(18)]
+		#[pallet::weight(T::WeightInfo::set_wagering_paused())]
+		pub fn set_wagering_paused(origin: OriginFor<T>, paused: bool) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			WageringPaused::<T>::put(paused);
+
+			if paused {
+				Self::deposit_event(Event::WageringPaused { available: Self::available_bankroll() });
+			} else {
+				Self::deposit_event(Event::WageringResumed);
+			}
+			Ok(())
+		}
+
+		/// Fund the house in exchange for shares of the bankroll, priced at its current value.
+		///
+		/// Providers take the variance of the house but no edge on the games: payouts are fair and
+		/// the rake goes to `RakeDestination`, so the wagers are worth nothing to the providers in
+		/// expectation. Their returns only come from the fees of global rounds, the house cut of
+		/// the lottery and the rounding of payouts. Nothing can be provided while the shares are
+		/// worth nothing.
+		#[pallet::call_index(19)]
+		#[pallet::weight(T::WeightInfo::provide_liquidity())]
+		pub fn provide_liquidity(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let shares = Self::mint_shares(&who, amount)?;
+			T::Currency::transfer(&who, &Self::account_id(), amount, ExistenceRequirement::KeepAlive)?;
+
+			Self::deposit_event(Event::LiquidityProvided { who, amount, shares });
+			Ok(())
+		}
+
+		/// Request the redemption of shares of the house. The shares are locked and redeemed
+		/// with `redeem_liquidity` once `LiquidityWithdrawalDelay` blocks have passed, for their
+		/// value at the request at most: they keep taking the losses of the house but no longer
+		/// its gains. The request expires `LiquidityRedemptionWindow` blocks after it unlocks. A
+		/// new request replaces the pending one.
+		#[pallet::call_index(20)]
+		#[pallet::weight(T::WeightInfo::withdraw_liquidity())]
+		pub fn withdraw_liquidity(origin: OriginFor<T>, shares: BalanceOf<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(!shares.is_zero(), Error::<T>::TooLittleLiquidity);
+
+			if let Some(pending) = LiquidityWithdrawals::<T>::take(&who) {
+				LiquidityShares::<T>::mutate(&who, |owned| {
+					*owned = owned.saturating_add(pending.shares)
+				});
+			}
+			// Locked shares leave the holdings of the owner but are only burned once redeemed
+			Self::take_shares(&who, shares)?;
+
+			let value = Self::share_value(shares);
+			let unlock_at = frame_system::Pallet::<T>::block_number()
+				.saturating_add(T::LiquidityWithdrawalDelay::get());
+			let expires_at = unlock_at.saturating_add(T::LiquidityRedemptionWindow::get());
+			LiquidityWithdrawals::<T>::insert(
+				&who,
+				LiquidityWithdrawal { shares, value, unlock_at, expires_at },
+			);
+
+			Self::deposit_event(Event::LiquidityWithdrawalRequested {
+				who,
+				shares,
+				value,
+				unlock_at,
+				expires_at,
+			});
+			Ok(())
+		}
+
+		/// Redeem the shares of an unlocked withdrawal request at their current value, capped to
+		/// their value at the request. The shares of an expired request are given back instead.
+		#[pallet::call_index(21)]
+		#[pallet::weight(T::WeightInfo::redeem_liquidity())]
+		pub fn redeem_liquidity(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let withdrawal =
+				LiquidityWithdrawals::<T>::get(&who).ok_or(Error::<T>::WithdrawalNotFound)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(now >= withdrawal.unlock_at, Error::<T>::WithdrawalLocked);
+
+			let shares = withdrawal.shares;
+			if now >= withdrawal.expires_at {
+				LiquidityWithdrawals::<T>::remove(&who);
+				LiquidityShares::<T>::mutate(&who, |owned| *owned = owned.saturating_add(shares));
+				Self::deposit_event(Event::LiquidityWithdrawalExpired { who, shares });
+				return Ok(())
+			}
+
+			// Gains made since the request stay with the other holders
+			let amount = Self::share_value(shares).min(withdrawal.value);
+			ensure!(!amount.is_zero(), Error::<T>::TooLittleLiquidity);
+			ensure!(amount <= Self::available_bankroll(), Error::<T>::InsufficientHouseFunds);
+
+			LiquidityWithdrawals::<T>::remove(&who);
+			TotalShares::<T>::mutate(|total| *total = total.saturating_sub(shares));
+			T::Currency::transfer(&Self::account_id(), &who, amount, ExistenceRequirement::AllowDeath)?;
+
+			Self::deposit_event(Event::LiquidityRedeemed { who, shares, amount });
+			Ok(())
+		}
+
+		/// Toss a coin with a stake in an asset accepted for wagers, resolved against a future
+		/// beacon round. Wagering in assets is not subject to the rake nor backed by the
+		/// liquidity providers, but its bankroll is checked like the native one.
+		#[pallet::call_index(22)]
+		#[pallet::weight(T::WeightInfo::toss_coin_with_asset())]
+		pub fn toss_coin_with_asset(
+			origin: OriginFor<T>,
+			asset: AssetIdOf<T>,
+			coin_side: CoinSide,
+			stake: AssetBalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let coin = Self::get_coin(&who)?;
+			Self::note_tosses(&who, 1)?;
+
+			let payout = Self::asset_payout_for(stake, coin.head_probability, &coin_side);
+			Self::place_asset_wager(asset, &who, stake, payout)?;
+			Self::queue_wager(
+				Self::next_toss_id(),
+				&who,
+				WagerKind::AssetToss {
+					asset,
+					guess: coin_side,
+					head_probability: coin.head_probability,
+					stake,
+					payout,
+				},
+			)
+		}
+
+		/// Accept wagers in an asset within the given limits, or stop accepting them.
+		#[pallet::call_index(23)]
+		#[pallet::weight(T::WeightInfo::set_asset_bet_limits())]
+		pub fn set_asset_bet_limits(
+			origin: OriginFor<T>,
+			asset: AssetIdOf<T>,
+			limits: Option<BetLimits<AssetBalanceOf<T>>>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			if let Some(limits) = &limits {
+				ensure!(limits.min_bet <= limits.max_bet, Error::<T>::InvalidBetLimits);
+			}
+			AssetBetLimits::<T>::set(asset, limits.clone());
+
+			Self::deposit_event(Event::AssetBetLimitsSet { asset, limits });
+			Ok(())
+		}
+
+		/// Add funds to the house bankroll of an asset.
+		#[pallet::call_index(24)]
+		#[pallet::weight(T::WeightInfo::fund_asset_house())]
+		pub fn fund_asset_house(
+			origin: OriginFor<T>,
+			asset: AssetIdOf<T>,
+			amount: AssetBalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			T::Assets::transfer(asset, &who, &Self::account_id(), amount, Preservation::Preserve)?;
+
+			Self::deposit_event(Event::AssetHouseFunded { who, asset, amount });
+			Ok(())
+		}
+
+		/// Withdraw funds from the house bankroll of an asset not owed to pending wagers.
+		#[pallet::call_index(25)]
+		#[pallet::weight(T::WeightInfo::withdraw_asset_from_house())]
+		pub fn withdraw_asset_from_house(
+			origin: OriginFor<T>,
+			asset: AssetIdOf<T>,
+			dest: T::AccountId,
+			amount: AssetBalanceOf<T>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			ensure!(amount <= Self::asset_bankroll(asset), Error::<T>::InsufficientHouseFunds);
+			T::Assets::transfer(asset, &Self::account_id(), &dest, amount, Preservation::Expendable)?;
+
+			Self::deposit_event(Event::AssetHouseWithdrawn { asset, dest, amount });
+			Ok(())
+		}
+
+		/// Set limits on your own wagering. Stricter limits take effect immediately, looser ones
+		/// after `LimitLooseningDelay` blocks.
+		#[pallet::call_index(26)]
+		#[pallet::weight(T::WeightInfo::set_play_limits())]
+		pub fn set_play_limits(
+			origin: OriginFor<T>,
+			limits: PlayLimits<BalanceOf<T>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let tightened = Self::play_limits(&who).strictest(&limits);
+			PlayerLimits::<T>::insert(&who, &tightened);
+			Self::deposit_event(Event::PlayLimitsSet { who: who.clone(), limits: tightened.clone() });
+
+			if tightened == limits {
+				PendingPlayerLimits::<T>::remove(&who);
+			} else {
+				let effective_at = frame_system::Pallet::<T>::block_number()
+					.saturating_add(T::LimitLooseningDelay::get());
+				PendingPlayerLimits::<T>::insert(
+					&who,
+					PendingPlayLimits { limits: limits.clone(), effective_at },
+				);
+				Self::deposit_event(Event::PlayLimitsScheduled { who, limits, effective_at });
+			}
+			Ok(())
+		}
+
+		/// Exclude yourself from wagering for the given number of blocks. A self-exclusion can
+		/// be extended but never shortened.
+		#[pallet::call_index(27)]
+		#[pallet::weight(T::WeightInfo::self_exclude())]
+		pub fn self_exclude(origin: OriginFor<T>, blocks: BlockNumberFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let until = frame_system::Pallet::<T>::block_number().saturating_add(blocks);
+			let until = SelfExclusions::<T>::get(&who).map_or(until, |current| current.max(until));
+			SelfExclusions::<T>::insert(&who, until);
+
+			Self::deposit_event(Event::SelfExcluded { who, until });
+			Ok(())
+		}
+
+		/// Withdraw winnings credited to your claimable balance, all of them without an amount.
+		/// Winnings of large payouts are withdrawn last and vest linearly. When the account has
+		/// no room for another vesting schedule, only the winnings that do not vest are paid.
+		#[pallet::call_index(28)]
+		#[pallet::weight(T::WeightInfo::claim())]
+		pub fn claim(origin: OriginFor<T>, amount: Option<BalanceOf<T>>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let claimable = Claimable::<T>::get(&who);
+			let mut amount = amount.unwrap_or(claimable);
+			ensure!(!amount.is_zero(), Error::<T>::NothingToClaim);
+			ensure!(amount <= claimable, Error::<T>::InsufficientClaimable);
+
+			let vesting = VestingClaimable::<T>::get(&who);
+			let mut vested = vesting.saturating_sub(claimable.saturating_sub(amount));
+			let now = frame_system::Pallet::<T>::block_number();
+			let mut schedule = None;
+			if let Some(rule) = PayoutVestingRule::<T>::get().filter(|_| !vested.is_zero()) {
+				let duration: BalanceOf<T> = rule.duration.saturated_into::<u32>().max(1).into();
+				// Rounded up so that the schedule ends within the duration
+				let per_block = vested.saturating_add(duration.saturating_sub(One::one())) / duration;
+				if T::Vesting::can_add_vesting_schedule(&who, vested, per_block, now).is_ok() {
+					schedule = Some(per_block);
+				} else {
+					// The winnings that vest stay claimable
+					amount = amount.saturating_sub(vested);
+					vested = Zero::zero();
+					ensure!(!amount.is_zero(), Error::<T>::TooManyVestingSchedules);
+				}
+			}
+
+			let remaining = claimable.saturating_sub(amount);
+			if remaining.is_zero() {
+				Claimable::<T>::remove(&who);
+			} else {
+				Claimable::<T>::insert(&who, remaining);
+			}
+			if vesting == vested {
+				VestingClaimable::<T>::remove(&who);
+			} else {
+				VestingClaimable::<T>::insert(&who, vesting.saturating_sub(vested));
+			}
+
+			Self::release(amount);
+			T::Currency::transfer(&Self::account_id(), &who, amount, ExistenceRequirement::AllowDeath)?;
+			if let Some(per_block) = schedule {
+				T::Vesting::add_vesting_schedule(&who, vested, per_block, now)?;
+				Self::deposit_event(Event::WinningsVested {
+					who: who.clone(),
+					amount: vested,
+					per_block,
+				});
+			}
+
+			Self::deposit_event(Event::WinningsClaimed { who, amount, remaining });
+			Ok(())
+		}
+
+		/// Set the threshold from which payouts vest and the duration of their vesting, or pay
+		/// every payout at once without a rule.
+		#[pallet::call_index(29)]
+		#[pallet::weight(T::WeightInfo::set_payout_vesting())]
+		pub fn set_payout_vesting(
+			origin: OriginFor<T>,
+			rule: Option<PayoutVesting<BalanceOf<T>, BlockNumberFor<T>>>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			if let Some(rule) = &rule {
+				ensure!(
+					!rule.threshold.is_zero() && !rule.duration.is_zero(),
+					Error::<T>::InvalidPayoutVesting
+				);
+			}
+			PayoutVestingRule::<T>::set(rule.clone());
+
+			Self::deposit_event(Event::PayoutVestingSet { rule });
+			Ok(())
+		}
+
+		/// Cash out the pending winnings of a double-or-nothing streak to your claimable
+		/// balance.
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::cash_out_streak(T::MaxExpiringStreaks::get()))]
+		pub fn cash_out_streak(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let streak = Streaks::<T>::take(&who).ok_or(Error::<T>::NoPendingWinnings)?;
+			Self::unschedule_streak_expiry(&who, streak.expires_at);
+
+			Self::settle_streak(&who, streak.pending)
+		}
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		fn validate_unsigned(source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			let Call::submit_beacon_pulse_unsigned { pulse } = call else {
+				return InvalidTransaction::Call.into()
+			};
+
+			// Pulses gossiped by other nodes would make every node pay for the verification, so
+			// only the pulses of the local off-chain worker and the ones already in a block are
+			// accepted. Anyone else submits signed.
+			if !matches!(source, TransactionSource::Local | TransactionSource::InBlock) {
+				return InvalidTransaction::Call.into()
+			}
+
+			// Only rounds that wagers wait on are worth the cost of the verification
+			if !BeaconWagers::<T>::contains_key(pulse.round) {
+				return InvalidTransaction::Stale.into()
+			}
+
+			if !Self::verify_beacon_pulse(pulse) {
+				return InvalidTransaction::BadProof.into()
+			}
+
+			ValidTransaction::with_tag_prefix("CoinGameBeacon")
+				.priority(T::BeaconUnsignedPriority::get())
+				.and_provides(pulse.round)
+				.longevity(BEACON_RESUBMIT_INTERVAL.into())
+				.propagate(false)
+				.build()
+		}
+	}
+
+	/// Frees the coin slot and the toss records of reaped accounts. Their losses are kept, so
+	/// that reaping an account does not reset its loss caps.
+	impl<T: Config> OnKilledAccount<T::AccountId> for Pallet<T> {
+		fn on_killed_account(who: &T::AccountId) {
+			if Self::get_coin(who).is_ok() {
+				Self::delete_coin(who);
+				Self::deposit_event(Event::CoinRemoved { who: who.clone() });
+			}
+			CoinLastActivity::<T>::remove(who);
+			TossActivities::<T>::remove(who);
+			FreeTossesUsed::<T>::remove(who);
+			JackpotStreaks::<T>::remove(who);
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		pub fn do_create_coin(
+			who: &T::AccountId,
+			head_probability: Option<Permill>,
+		) -> DispatchResult {
+			let head_probability = head_probability.unwrap_or(Permill::from_percent(50));
+			ensure!(
+				!head_probability.is_zero() && !head_probability.is_one(),
+				Error::<T>::InvalidBias
+			);
+
+			let mut coin = Coin { side: CoinSide::Head, head_probability };
+			coin.side = Self::random_coin_side_for(&coin);
+
+			if Self::get_coin(who).is_ok() {
+				return Err(Error::<T>::CoinAlreadyExists.into());
+			}
+			ensure!(
+				CoinStorage::<T>::get().map_or(0, |coins| coins.len()) < COIN_STORAGE_LIMIT as usize,
+				Error::<T>::TooManyCoins
+			);
+
+			T::NativeBalance::hold(&HoldReason::CoinDeposit.into(), who, T::CoinDeposit::get())?;
+			Self::insert_coin(who, coin);
+			Self::note_coin_activity(who);
 
 			Ok(())
 		}
@@ -152,44 +2196,158 @@ pub mod pallet {
 			}
 
 			Self::delete_coin(&who);
+			CoinLastActivity::<T>::remove(who);
+			Self::release_coin_deposit(who);
 
 			Ok(())
 		}
 
-		pub fn random_coin_side() -> CoinSide {
-			let block_number = <frame_system::Pallet<T>>::block_number();
-			let seed = block_number.try_into().unwrap_or_else(|_| 0u32);
+		/// Release the whole deposit held for the coin of `who`, returning its amount.
+		fn release_coin_deposit(who: &T::AccountId) -> BalanceOf<T> {
+			let reason = HoldReason::CoinDeposit.into();
+			let deposit = T::NativeBalance::balance_on_hold(&reason, who);
+			T::NativeBalance::release(&reason, who, deposit, Precision::BestEffort).unwrap_or_else(
+				|e| {
+					log::error!(target: LOG_TARGET, "Coin deposit not released: {:?}", e);
+					Zero::zero()
+				},
+			)
+		}
 
-			if Self::generate_insecure_random_boolean(seed) {
-				CoinSide::Head
-			} else {
-				CoinSide::Tail
+		fn note_coin_activity(who: &T::AccountId) {
+			CoinLastActivity::<T>::insert(who, frame_system::Pallet::<T>::block_number());
+		}
+
+		/// Remove the coins idle for `CoinExpiry` blocks at block `n`, as long as `limit` allows.
+		fn reap_expired_coins(n: BlockNumberFor<T>, limit: Weight) -> Weight {
+			let mut weight = T::DbWeight::get().reads_writes(1, 1);
+			// The last activity of the coin, then the holds and the account of the owner written
+			// when the deposit is released
+			let per_coin = T::DbWeight::get().reads_writes(3, 3);
+			if !limit.all_gte(weight) {
+				return Weight::zero()
+			}
+
+			let Some(mut coins) = CoinStorage::<T>::get() else { return weight };
+
+			let owners: Vec<T::AccountId> = coins.keys().cloned().collect();
+			let mut expired = false;
+			for who in owners {
+				if !limit.all_gte(weight.saturating_add(per_coin)) {
+					break
+				}
+				weight.saturating_accrue(per_coin);
+
+				let Some(last_activity) = CoinLastActivity::<T>::get(&who) else {
+					// Coins created before activity was recorded start their expiry now
+					CoinLastActivity::<T>::insert(&who, n);
+					continue
+				};
+				if n < last_activity.saturating_add(T::CoinExpiry::get()) {
+					continue
+				}
+
+				coins.remove(&who);
+				CoinLastActivity::<T>::remove(&who);
+				let deposit = Self::release_coin_deposit(&who);
+				expired = true;
+				Self::deposit_event(Event::CoinExpired { who, last_activity, deposit });
+			}
+
+			if expired {
+				CoinStorage::<T>::put(coins);
 			}
+
+			weight
+		}
+
+		/// Toss a coin honouring its bias.
+		pub fn random_coin_side_for(coin: &Coin) -> CoinSide {
+			let outcome = Self::random_number(Permill::ACCURACY);
+			CoinSide::from_biased_outcome(outcome, coin.head_probability)
+		}
+
+		/// Check that `who` may toss `tosses` more times at the current block, returning the
+		/// updated activity of the account.
+		pub fn check_toss_rate(
+			who: &T::AccountId,
+			tosses: u32,
+		) -> Result<TossActivity<BlockNumberFor<T>>, Error<T>> {
+			let now = frame_system::Pallet::<T>::block_number();
+			let period = (now / T::TossPeriod::get().max(One::one())).saturated_into();
+
+			let previous = match TossActivities::<T>::get(who) {
+				Some(activity) => {
+					ensure!(
+						now >= activity.last_toss.saturating_add(T::TossCooldown::get()),
+						Error::<T>::TooSoon
+					);
+					if activity.period == period { activity.tosses } else { 0 }
+				},
+				None => 0,
+			};
+
+			let tosses = previous.saturating_add(tosses);
+			ensure!(tosses <= T::MaxTossesPerPeriod::get(), Error::<T>::TooSoon);
+
+			Ok(TossActivity { last_toss: now, period, tosses })
+		}
+
+		fn note_tosses(who: &T::AccountId, tosses: u32) -> DispatchResult {
+			let activity = Self::check_toss_rate(who, tosses)?;
+			TossActivities::<T>::insert(who, activity);
+			Self::note_coin_activity(who);
+			Ok(())
+		}
+
+		/// Use one of the free tosses of `who` in the current period, if any is left.
+		fn use_free_toss(who: &T::AccountId) -> Pays {
+			let now = frame_system::Pallet::<T>::block_number();
+			let period: u32 = (now / T::FreeTossPeriod::get().max(One::one())).saturated_into();
+
+			FreeTossesUsed::<T>::mutate(who, |(used_period, used)| {
+				if *used_period != period {
+					*used_period = period;
+					*used = 0;
+				}
+				if *used < T::FreeTosses::get() {
+					*used += 1;
+					Pays::No
+				} else {
+					Pays::Yes
+				}
+			})
+		}
+
+		fn next_toss_id() -> TossId {
+			NextTossId::<T>::mutate(|id| {
+				let toss_id = *id;
+				*id = id.wrapping_add(1);
+				toss_id
+			})
 		}
 
 		pub fn insert_coin(who: &T::AccountId, coin: Coin) {
-			let bounded_btree = CoinStorage::<T>::get();
-			if let Some(mut map) = bounded_btree {
-				let _ = map.try_insert(who.clone(), coin);
-			} else {
-				let mut map = BoundedBTreeMap::<AccountIdOf<T>, Coin, ConstU32<COIN_STORAGE_LIMIT>>::new();
+			CoinStorage::<T>::mutate(|bounded_btree| {
+				let map = bounded_btree.get_or_insert_with(BoundedBTreeMap::new);
 				let _ = map.try_insert(who.clone(), coin);
-				let _ = CoinStorage::<T>::set(Some(map));
-			}
+			});
 		}
 
 		pub fn mutate_coin(who: &T::AccountId, coin: Coin) {
-			let bounded_btree = CoinStorage::<T>::get();
-			if let Some(mut map) = bounded_btree {
-				let _ = map.get_mut(who).map(|c| *c = coin);
-			}
+			CoinStorage::<T>::mutate(|bounded_btree| {
+				if let Some(map) = bounded_btree {
+					let _ = map.get_mut(who).map(|c| *c = coin);
+				}
+			});
 		}
 
 		pub fn delete_coin(who: &T::AccountId) {
-			let bounded_btree = CoinStorage::<T>::get();
-			if let Some(mut map) = bounded_btree {
-				let _ = map.remove(who);
-			}
+			CoinStorage::<T>::mutate(|bounded_btree| {
+				if let Some(map) = bounded_btree {
+					let _ = map.remove(who);
+				}
+			});
 		}
 
 		pub fn get_coin(who: &T::AccountId) ->  Result<Coin, Error<T>> {
@@ -206,14 +2364,1610 @@ pub mod pallet {
 		}
 
 
-		// TODO: check for safer alternatives to generate random numbers in this env
-		// You should call this function with different seed values, in this case I'm are using the block number as seed
-		pub fn generate_insecure_random_boolean(seed: u32) -> bool {
-			let pallet_id = T::PalletId::get();
-			let (random_seed, _) = T::Randomness::random(&(pallet_id, seed).encode());
-			let random_number = <u32>::decode(&mut random_seed.as_ref())
+		/// The account holding the house funds.
+		pub fn account_id() -> T::AccountId {
+			T::PalletId::get().into_account_truncating()
+		}
+
+		/// Amount paid for a winning stake on a game with `sides` equally likely outcomes.
+		pub fn payout_for(stake: BalanceOf<T>, sides: u32) -> BalanceOf<T> {
+			stake.saturating_mul(sides.into())
+		}
+
+		/// Amount paid for a winning stake on `guess`: the stake divided by the probability of
+		/// `guess`, rounded down so that a biased coin never has a positive expected value for
+		/// the player.
+		pub fn coin_payout_for(
+			stake: BalanceOf<T>,
+			head_probability: Permill,
+			guess: &CoinSide,
+		) -> BalanceOf<T> {
+			let probability = match guess {
+				CoinSide::Head => head_probability,
+				CoinSide::Tail => head_probability.left_from_one(),
+			};
+			probability.saturating_reciprocal_mul_floor(stake)
+		}
+
+		/// Amount paid for a batch of winning stakes of `stake` on every guess.
+		pub fn batch_payout_for(
+			stake: BalanceOf<T>,
+			head_probability: Permill,
+			guesses: &[CoinSide],
+		) -> BalanceOf<T> {
+			guesses.iter().fold(Zero::zero(), |total: BalanceOf<T>, guess| {
+				total.saturating_add(Self::coin_payout_for(stake, head_probability, guess))
+			})
+		}
+
+		/// Charge the rake of a house-backed wager and commit its payout until the wager is
+		/// resolved. The stake itself is held in escrow when the wager is queued. A zero stake
+		/// is a free game and moves no funds.
+		pub fn place_wager(
+			who: &T::AccountId,
+			stake: BalanceOf<T>,
+			payout: BalanceOf<T>,
+		) -> DispatchResult {
+			if stake.is_zero() {
+				return Ok(())
+			}
+
+			Self::charge_wager(who, stake, payout)?;
+			Self::commit(payout);
+			Ok(())
+		}
+
+		/// Release the payout committed to a wager, and pay it out when the wager is won.
+		/// Returns the amount paid to the player.
+		pub fn settle_wager(
+			who: &T::AccountId,
+			payout: BalanceOf<T>,
+			won: bool,
+		) -> Result<BalanceOf<T>, DispatchError> {
+			Self::release(payout);
+			if !won {
+				return Ok(Zero::zero())
+			}
+
+			Self::pay_out(who, payout)?;
+			Ok(payout)
+		}
+
+		/// Amount paid in an asset for a winning stake on `guess`, rounded down like
+		/// [`Self::coin_payout_for`].
+		pub fn asset_payout_for(
+			stake: AssetBalanceOf<T>,
+			head_probability: Permill,
+			guess: &CoinSide,
+		) -> AssetBalanceOf<T> {
+			let probability = match guess {
+				CoinSide::Head => head_probability,
+				CoinSide::Tail => head_probability.left_from_one(),
+			};
+			probability
+				.saturating_reciprocal_mul_floor(stake.saturated_into::<u128>())
+				.saturated_into()
+		}
+
+		/// Take a stake in an asset into the house bankroll of the asset and commit its payout
+		/// until the wager is resolved. The bankroll of the asset is checked in the native
+		/// currency like the native bankroll.
+		pub fn place_asset_wager(
+			asset: AssetIdOf<T>,
+			who: &T::AccountId,
+			stake: AssetBalanceOf<T>,
+			payout: AssetBalanceOf<T>,
+		) -> DispatchResult {
+			let limits = AssetBetLimits::<T>::get(asset).ok_or(Error::<T>::AssetNotAccepted)?;
+			ensure!(
+				limits.min_bet <= stake && stake <= limits.max_bet,
+				Error::<T>::StakeOutOfLimits
+			);
+
+			let native_stake = Self::native_value(&limits, stake);
+			let available = Self::native_value(&limits, Self::asset_bankroll(asset))
+				.saturating_sub(T::SolvencyBuffer::get());
+			Self::ensure_bankroll_can_back(
+				available,
+				native_stake,
+				Self::native_value(&limits, payout),
+			)?;
+			Self::ensure_within_limits(who, native_stake, native_stake)?;
+
+			T::Assets::transfer(asset, who, &Self::account_id(), stake, Preservation::Preserve)?;
+			AssetCommitted::<T>::mutate(asset, |committed| {
+				*committed = committed.saturating_add(payout)
+			});
+			Ok(())
+		}
+
+		/// Release the payout in an asset committed to a wager, and pay it out when the wager is
+		/// won. Returns the amount paid to the player.
+		pub fn settle_asset_wager(
+			asset: AssetIdOf<T>,
+			who: &T::AccountId,
+			payout: AssetBalanceOf<T>,
+			won: bool,
+		) -> Result<AssetBalanceOf<T>, DispatchError> {
+			AssetCommitted::<T>::mutate(asset, |committed| {
+				*committed = committed.saturating_sub(payout)
+			});
+			if !won {
+				return Ok(Zero::zero())
+			}
+
+			T::Assets::transfer(asset, &Self::account_id(), who, payout, Preservation::Expendable)?;
+			// Limits removed since the wager was placed leave the losses of the player as is
+			if let Some(limits) = AssetBetLimits::<T>::get(asset) {
+				Self::note_winnings(who, Self::native_value(&limits, payout));
+			}
+			Ok(payout)
+		}
+
+		/// Value of an amount of an asset in the native currency.
+		fn native_value(
+			limits: &BetLimits<AssetBalanceOf<T>>,
+			amount: AssetBalanceOf<T>,
+		) -> BalanceOf<T> {
+			limits.native_rate.saturating_mul_int(amount.saturated_into::<u128>()).saturated_into()
+		}
+
+		/// House bankroll of an asset: the balance of the house account in the asset not owed to
+		/// pending wagers.
+		pub fn asset_bankroll(asset: AssetIdOf<T>) -> AssetBalanceOf<T> {
+			<T::Assets as fungibles::Inspect<T::AccountId>>::reducible_balance(
+				asset,
+				&Self::account_id(),
+				Preservation::Expendable,
+				Fortitude::Polite,
+			)
+			.saturating_sub(AssetCommitted::<T>::get(asset))
+		}
+
+		/// Move a stake into the house. A non-zero `payout` makes it a house-backed wager, which
+		/// the bankroll has to be able to cover and which charges the rake on top of the stake,
+		/// so that the whole stake backs the payout.
+		pub fn take_stake(
+			who: &T::AccountId,
+			stake: BalanceOf<T>,
+			payout: BalanceOf<T>,
+		) -> DispatchResult {
+			if stake.is_zero() {
+				return Ok(())
+			}
+
+			Self::charge_wager(who, stake, payout)?;
+			T::Currency::transfer(who, &Self::account_id(), stake, ExistenceRequirement::KeepAlive)
+		}
+
+		/// Check a stake against the bankroll, when it backs a non-zero `payout`, and against
+		/// the play limits of `who`, and charge the rake of a house-backed wager. The rake is
+		/// kept when the wager is refunded.
+		fn charge_wager(
+			who: &T::AccountId,
+			stake: BalanceOf<T>,
+			payout: BalanceOf<T>,
+		) -> DispatchResult {
+			let mut rake = Zero::zero();
+			if !payout.is_zero() {
+				rake = T::Rake::get().mul_floor(stake);
+				Self::ensure_can_back(stake, payout)?;
+			}
+
+			Self::ensure_within_limits(who, stake, stake.saturating_add(rake))?;
+
+			if !rake.is_zero() {
+				let imbalance = T::Currency::withdraw(
+					who,
+					rake,
+					WithdrawReasons::FEE,
+					ExistenceRequirement::KeepAlive,
+				)?;
+				T::RakeDestination::on_unbalanced(imbalance);
+				Self::deposit_event(Event::RakeCollected { who: who.clone(), amount: rake });
+			}
+
+			Ok(())
+		}
+
+		/// Check that the bankroll can back a wager of `stake`, not yet in the house, paying
+		/// `payout` when won.
+		pub fn ensure_can_back(stake: BalanceOf<T>, payout: BalanceOf<T>) -> DispatchResult {
+			Self::ensure_bankroll_can_back(Self::available_bankroll(), stake, payout)
+		}
+
+		/// Check that a bankroll with `available` funds at risk can back a wager of `stake`, not
+		/// yet in the house, paying `payout` when won. Native and asset wagers are checked
+		/// alike, in the native currency.
+		fn ensure_bankroll_can_back(
+			available: BalanceOf<T>,
+			stake: BalanceOf<T>,
+			payout: BalanceOf<T>,
+		) -> DispatchResult {
+			ensure!(!WageringPaused::<T>::get(), Error::<T>::WageringPaused);
+
+			ensure!(available.saturating_add(stake) >= payout, Error::<T>::InsufficientHouseFunds);
+			ensure!(stake <= T::MaxBetFraction::get().mul_floor(available), Error::<T>::StakeTooHigh);
+
+			Ok(())
+		}
+
+		/// Part of the house balance that can be put at risk: the balance not committed to
+		/// players nor kept as solvency buffer.
+		pub fn available_bankroll() -> BalanceOf<T> {
+			T::Currency::free_balance(&Self::account_id())
+				.saturating_sub(Committed::<T>::get())
+				.saturating_sub(T::SolvencyBuffer::get())
+		}
+
+		/// Credit winnings to the claimable balance of a player, pausing wagering when the
+		/// bankroll falls under the circuit breaker threshold. Payouts from the vesting threshold
+		/// vest once claimed.
+		pub fn pay_out(who: &T::AccountId, payout: BalanceOf<T>) -> DispatchResult {
+			if payout.is_zero() {
+				return Ok(())
+			}
+
+			ensure!(payout <= Self::liquidity_value(), Error::<T>::InsufficientHouseFunds);
+			Claimable::<T>::mutate(who, |claimable| *claimable = claimable.saturating_add(payout));
+			if PayoutVestingRule::<T>::get().map_or(false, |rule| payout >= rule.threshold) {
+				VestingClaimable::<T>::mutate(who, |vesting| *vesting = vesting.saturating_add(payout));
+			}
+			Self::commit(payout);
+			Self::note_winnings(who, payout);
+			Self::deposit_event(Event::WinningsCredited { who: who.clone(), amount: payout });
+
+			let available = Self::available_bankroll();
+			if available < T::CircuitBreakerThreshold::get() && !WageringPaused::<T>::get() {
+				WageringPaused::<T>::put(true);
+				Self::deposit_event(Event::WageringPaused { available });
+			}
+
+			Ok(())
+		}
+
+		/// Weight of [`Self::pay_out`]: the claimable, vesting and losses records of the player,
+		/// the committed funds, the house account, the vesting rule and the wagering pause.
+		pub fn pay_out_weight() -> Weight {
+			T::DbWeight::get().reads_writes(7, 5)
+		}
+
+		/// Value of the house owned by the share holders: its balance not committed to players.
+		pub fn liquidity_value() -> BalanceOf<T> {
+			T::Currency::free_balance(&Self::account_id()).saturating_sub(Committed::<T>::get())
+		}
+
+		/// Current value of `shares`, rounded down.
+		pub fn share_value(shares: BalanceOf<T>) -> BalanceOf<T> {
+			Self::pro_rata(Self::liquidity_value(), shares, TotalShares::<T>::get())
+		}
+
+		/// Attribute the value of the house to the house account when no share exists yet, so
+		/// that the first provider does not get the funds put in before any share was minted.
+		fn issue_house_shares() {
+			if !TotalShares::<T>::get().is_zero() {
+				return
+			}
+
+			let value = Self::liquidity_value();
+			LiquidityShares::<T>::mutate(Self::account_id(), |shares| {
+				*shares = shares.saturating_add(value)
+			});
+			TotalShares::<T>::put(value);
+		}
+
+		/// Mint the shares bought by `amount`, not yet in the house, for `owner`. The shares are
+		/// rounded down so that providing liquidity never dilutes the existing holders.
+		///
+		/// The first shares of a worthless house bought by a provider lock `MinimumLiquidity` of
+		/// them in the house, so that inflating the price of a share by donating to the house costs the donor far
+		/// more than the rounding it takes from the next providers.
+		fn mint_shares(
+			owner: &T::AccountId,
+			amount: BalanceOf<T>,
+		) -> Result<BalanceOf<T>, DispatchError> {
+			Self::issue_house_shares();
+
+			let mut total = TotalShares::<T>::get();
+			let shares = if total.is_zero() && *owner == Self::account_id() {
+				amount
+			} else if total.is_zero() {
+				let minimum = T::MinimumLiquidity::get();
+				LiquidityShares::<T>::mutate(Self::account_id(), |shares| {
+					*shares = shares.saturating_add(minimum)
+				});
+				total = minimum;
+				amount.saturating_sub(minimum)
+			} else {
+				// Shares of a drained house are worth nothing, and no amount can be priced in them
+				let value = Self::liquidity_value();
+				ensure!(!value.is_zero(), Error::<T>::TooLittleLiquidity);
+				multiply_by_rational_with_rounding(
+					amount.saturated_into(),
+					total.saturated_into(),
+					value.saturated_into(),
+					Rounding::Down,
+				)
+				.map(|shares| shares.saturated_into())
+				.ok_or(Error::<T>::TooLittleLiquidity)?
+			};
+			ensure!(!shares.is_zero(), Error::<T>::TooLittleLiquidity);
+
+			LiquidityShares::<T>::mutate(owner, |owned| *owned = owned.saturating_add(shares));
+			TotalShares::<T>::put(total.saturating_add(shares));
+
+			Ok(shares)
+		}
+
+		/// Burn the shares of `owner` worth `amount`, rounded up so that withdrawing never
+		/// dilutes the remaining holders.
+		fn burn_shares_for(owner: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+			Self::issue_house_shares();
+
+			let shares = multiply_by_rational_with_rounding(
+				amount.saturated_into(),
+				TotalShares::<T>::get().saturated_into(),
+				Self::liquidity_value().saturated_into(),
+				Rounding::Up,
+			)
+			.map(|shares| shares.saturated_into())
+			.ok_or(Error::<T>::InsufficientShares)?;
+
+			Self::burn_shares(owner, shares)
+		}
+
+		fn burn_shares(owner: &T::AccountId, shares: BalanceOf<T>) -> DispatchResult {
+			Self::take_shares(owner, shares)?;
+			TotalShares::<T>::mutate(|total| *total = total.saturating_sub(shares));
+			Ok(())
+		}
+
+		/// Remove `shares` from the holdings of `owner` without burning them.
+		fn take_shares(owner: &T::AccountId, shares: BalanceOf<T>) -> DispatchResult {
+			LiquidityShares::<T>::try_mutate_exists(owner, |owned| {
+				let remaining = owned
+					.unwrap_or_else(Zero::zero)
+					.checked_sub(&shares)
+					.ok_or(Error::<T>::InsufficientShares)?;
+				*owned = Some(remaining).filter(|remaining| !remaining.is_zero());
+				Ok(())
+			})
+		}
+
+		/// Play limits of `who` in effect, looser limits included once their delay has passed.
+		pub fn play_limits(who: &T::AccountId) -> PlayLimits<BalanceOf<T>> {
+			match PendingPlayerLimits::<T>::get(who) {
+				Some(pending)
+					if frame_system::Pallet::<T>::block_number() >= pending.effective_at =>
+					pending.limits,
+				_ => PlayerLimits::<T>::get(who),
+			}
+		}
+
+		/// Store the looser limits of `who` whose delay has passed, returning the limits in
+		/// effect.
+		fn apply_pending_limits(who: &T::AccountId) -> PlayLimits<BalanceOf<T>> {
+			match PendingPlayerLimits::<T>::get(who) {
+				Some(pending)
+					if frame_system::Pallet::<T>::block_number() >= pending.effective_at =>
+				{
+					PendingPlayerLimits::<T>::remove(who);
+					PlayerLimits::<T>::insert(who, &pending.limits);
+					Self::deposit_event(Event::PlayLimitsSet {
+						who: who.clone(),
+						limits: pending.limits.clone(),
+					});
+					pending.limits
+				},
+				_ => PlayerLimits::<T>::get(who),
+			}
+		}
+
+		fn ensure_not_self_excluded(who: &T::AccountId) -> DispatchResult {
+			if let Some(until) = SelfExclusions::<T>::get(who) {
+				ensure!(frame_system::Pallet::<T>::block_number() >= until, Error::<T>::SelfExcluded);
+				SelfExclusions::<T>::remove(who);
+			}
+			Ok(())
+		}
+
+		/// Net losses of `who` in the current day and week.
+		pub fn losses(who: &T::AccountId) -> PlayerLosses<BalanceOf<T>> {
+			let day_length = T::DayLength::get().max(One::one());
+			let now = frame_system::Pallet::<T>::block_number();
+			let day: u32 = (now / day_length).saturated_into();
+			let week = day / 7;
+
+			let mut losses = Losses::<T>::get(who);
+			if losses.day != day {
+				losses.day = day;
+				losses.daily = Zero::zero();
+			}
+			if losses.week != week {
+				losses.week = week;
+				losses.weekly = Zero::zero();
+			}
+			losses
+		}
+
+		/// Check a wager of `stake` against the self-exclusion and the play limits of `who`,
+		/// counting `at_risk` as lost until winnings are paid out. Every wager goes through this
+		/// check.
+		fn ensure_within_limits(
+			who: &T::AccountId,
+			stake: BalanceOf<T>,
+			at_risk: BalanceOf<T>,
+		) -> DispatchResult {
+			Self::ensure_not_self_excluded(who)?;
+
+			let limits = Self::apply_pending_limits(who);
+			let mut losses = Self::losses(who);
+			losses.daily = losses.daily.saturating_add(at_risk);
+			losses.weekly = losses.weekly.saturating_add(at_risk);
+
+			ensure!(limits.stake_cap.map_or(true, |cap| stake <= cap), Error::<T>::StakeCapExceeded);
+			ensure!(
+				limits.daily_loss_cap.map_or(true, |cap| losses.daily <= cap),
+				Error::<T>::DailyLossCapReached
+			);
+			ensure!(
+				limits.weekly_loss_cap.map_or(true, |cap| losses.weekly <= cap),
+				Error::<T>::WeeklyLossCapReached
+			);
+
+			Losses::<T>::insert(who, losses);
+			Ok(())
+		}
+
+		fn note_winnings(who: &T::AccountId, amount: BalanceOf<T>) {
+			let mut losses = Self::losses(who);
+			losses.daily = losses.daily.saturating_sub(amount);
+			losses.weekly = losses.weekly.saturating_sub(amount);
+			Losses::<T>::insert(who, losses);
+		}
+
+		/// Record a staked coin toss towards the jackpot: a share of a losing stake feeds the
+		/// jackpot, and `JackpotStreak` tosses guessed right in a row win it. Tosses staking less
+		/// than `MinJackpotStake` do not count, so that the jackpot cannot be farmed with dust.
+		fn note_jackpot_toss(who: &T::AccountId, stake: BalanceOf<T>, won: bool) -> DispatchResult {
+			if stake.is_zero() || stake < T::MinJackpotStake::get() {
+				return Ok(())
+			}
+
+			if !won {
+				JackpotStreaks::<T>::remove(who);
+				Self::feed_jackpot(stake);
+				return Ok(())
+			}
+
+			let streak = JackpotStreaks::<T>::get(who).saturating_add(1);
+			if streak < T::JackpotStreak::get() {
+				JackpotStreaks::<T>::insert(who, streak);
+				return Ok(())
+			}
+
+			JackpotStreaks::<T>::remove(who);
+			let amount = JackpotPot::<T>::take();
+			if amount.is_zero() {
+				return Ok(())
+			}
+
+			Self::release(amount);
+			Self::pay_out(who, amount)?;
+
+			let index = JackpotWinCount::<T>::mutate(|count| {
+				let index = *count;
+				*count = count.saturating_add(1);
+				index
+			});
+			let block = frame_system::Pallet::<T>::block_number();
+			JackpotWins::<T>::insert(index, JackpotWin { winner: who.clone(), amount, block });
+
+			Self::deposit_event(Event::JackpotWon { who: who.clone(), index, amount });
+			Ok(())
+		}
+
+		/// Add the share of a lost stake owed to the jackpot to the pot.
+		fn feed_jackpot(stake: BalanceOf<T>) {
+			let contribution = T::JackpotContribution::get().mul_floor(stake);
+			Self::commit(contribution);
+			JackpotPot::<T>::mutate(|pot| *pot = pot.saturating_add(contribution));
+		}
+
+		/// Hold `amount` of `who` in escrow for `purpose`. The stake is refunded `EscrowTimeout`
+		/// blocks after `resolves_at` if the wager is still unresolved by then.
+		fn hold_in_escrow(
+			purpose: EscrowPurpose,
+			who: &T::AccountId,
+			amount: BalanceOf<T>,
+			resolves_at: BlockNumberFor<T>,
+		) -> DispatchResult {
+			if amount.is_zero() {
+				return Ok(())
+			}
+
+			Self::ensure_within_limits(who, amount, amount)?;
+			let timeout_at = resolves_at.saturating_add(T::EscrowTimeout::get());
+			Self::schedule_escrow_timeout(timeout_at, &purpose, who)?;
+			Self::hold_escrow(purpose, who, amount, timeout_at)
+		}
+
+		/// Hold `amount` of `who` for `purpose` until `timeout_at`, already scheduled.
+		fn hold_escrow(
+			purpose: EscrowPurpose,
+			who: &T::AccountId,
+			amount: BalanceOf<T>,
+			timeout_at: BlockNumberFor<T>,
+		) -> DispatchResult {
+			T::NativeBalance::hold(&HoldReason::Escrow.into(), who, amount)?;
+			Escrows::<T>::insert(&purpose, who, Escrow { amount, timeout_at });
+
+			Self::deposit_event(Event::EscrowHeld { purpose, who: who.clone(), amount, timeout_at });
+			Ok(())
+		}
+
+		/// Refund the escrow of `who` for `purpose` at `timeout_at` if its wager is unresolved.
+		fn schedule_escrow_timeout(
+			timeout_at: BlockNumberFor<T>,
+			purpose: &EscrowPurpose,
+			who: &T::AccountId,
+		) -> DispatchResult {
+			EscrowTimeouts::<T>::try_mutate(timeout_at, |escrows| {
+				escrows
+					.try_push((purpose.clone(), who.clone()))
+					.map_err(|_| Error::<T>::TooManyEscrowTimeouts)
+			})?;
+			Ok(())
+		}
+
+		/// Move the escrowed stake of `who` for `purpose` to the house, returning the amount
+		/// moved. Nothing is moved when the escrow was already released.
+		fn settle_escrow(purpose: &EscrowPurpose, who: &T::AccountId) -> BalanceOf<T> {
+			let Some(escrow) = Escrows::<T>::take(purpose, who) else { return Zero::zero() };
+
+			match T::NativeBalance::transfer_on_hold(
+				&HoldReason::Escrow.into(),
+				who,
+				&Self::account_id(),
+				escrow.amount,
+				Precision::BestEffort,
+				Restriction::Free,
+				Fortitude::Force,
+			) {
+				Ok(amount) => {
+					Self::deposit_event(Event::EscrowSettled {
+						purpose: purpose.clone(),
+						who: who.clone(),
+						amount,
+					});
+					amount
+				},
+				Err(e) => {
+					log::error!(target: LOG_TARGET, "Escrow not settled: {:?}", e);
+					// Left to be refunded at its timeout
+					Escrows::<T>::insert(purpose, who, escrow);
+					Zero::zero()
+				},
+			}
+		}
+
+		/// Give the escrowed stake of `who` for `purpose` back to them, returning the amount
+		/// released.
+		fn release_escrow(
+			purpose: &EscrowPurpose,
+			who: &T::AccountId,
+			timed_out: bool,
+		) -> BalanceOf<T> {
+			let Some(escrow) = Escrows::<T>::take(purpose, who) else { return Zero::zero() };
+
+			match T::NativeBalance::release(
+				&HoldReason::Escrow.into(),
+				who,
+				escrow.amount,
+				Precision::BestEffort,
+			) {
+				Ok(amount) => {
+					Self::note_winnings(who, amount);
+					Self::deposit_event(Event::EscrowReleased {
+						purpose: purpose.clone(),
+						who: who.clone(),
+						amount,
+						timed_out,
+					});
+					amount
+				},
+				Err(e) => {
+					log::error!(target: LOG_TARGET, "Escrow not released: {:?}", e);
+					Escrows::<T>::insert(purpose, who, escrow);
+					Zero::zero()
+				},
+			}
+		}
+
+		/// Refund the escrows timing out at block `n` whose wager is still unresolved, and the
+		/// wagers still waiting for their beacon round.
+		fn refund_timed_out_escrows(n: BlockNumberFor<T>) -> Weight {
+			let timed_out = EscrowTimeouts::<T>::take(n);
+			let count = timed_out.len() as u64;
+
+			let mut weight = T::DbWeight::get().reads_writes(1 + 2 * count, 1 + 3 * count);
+			for (purpose, who) in timed_out {
+				match purpose {
+					EscrowPurpose::Wager { round, toss_id } => {
+						weight.saturating_accrue(Self::refund_timed_out_wager(round, toss_id));
+					},
+					EscrowPurpose::Series => {
+						weight.saturating_accrue(Self::refund_timed_out_series(n, &who));
+					},
+					_ => {
+						Self::release_escrow(&purpose, &who, true);
+					},
+				}
+			}
+
+			weight
+		}
+
+		/// Refund the wager `toss_id` if it is still waiting for the beacon round `round`.
+		fn refund_timed_out_wager(round: u64, toss_id: TossId) -> Weight {
+			let mut wagers = BeaconWagers::<T>::get(round);
+			let Some(index) = wagers.iter().position(|wager| wager.toss_id == toss_id) else {
+				return T::DbWeight::get().reads(1)
+			};
+
+			let wager = wagers.remove(index);
+			if wagers.is_empty() {
+				BeaconWagers::<T>::remove(round);
+			} else {
+				BeaconWagers::<T>::insert(round, wagers);
+			}
+
+			if let Err(e) = Self::refund_wager(round, wager, true) {
+				log::error!(target: LOG_TARGET, "Wager {} not refunded: {:?}", toss_id, e);
+			}
+
+			// The wagers of the round, the asset accounts, and the pay out of a rolled streak
+			T::DbWeight::get().reads_writes(3, 3).saturating_add(Self::pay_out_weight())
+		}
+
+		/// Close the series of `who` timing out at block `n` if it is still open, refunding its
+		/// stake and releasing its payout. A toss still pending is refunded at its own timeout.
+		fn refund_timed_out_series(n: BlockNumberFor<T>, who: &T::AccountId) -> Weight {
+			// The series was resolved in time, and may have been replaced by a newer one
+			let purpose = EscrowPurpose::Series;
+			if Escrows::<T>::get(&purpose, who).map_or(true, |escrow| escrow.timeout_at != n) {
+				return T::DbWeight::get().reads(1)
+			}
+			let Some(series) = OpenSeries::<T>::take(who) else {
+				return T::DbWeight::get().reads(2)
+			};
+
+			SeriesTosses::<T>::remove(who);
+			Self::release(Self::payout_for(series.stake, COIN_SIDES));
+			Self::release_escrow(&purpose, who, true);
+			Self::deposit_event(Event::SeriesExpired { who: who.clone(), stake: series.stake });
+
+			// The series, its pending toss and the committed funds
+			T::DbWeight::get().reads_writes(3, 3)
+		}
+
+		/// Give the stake of a wager that waited for the beacon round `round` back to the
+		/// player and release its payout. The winnings rolled into a streak are paid out.
+		fn refund_wager(round: u64, wager: BeaconWager<T>, timed_out: bool) -> DispatchResult {
+			let BeaconWager { toss_id, who, kind } = wager;
+			Self::release_escrow(&EscrowPurpose::Wager { round, toss_id }, &who, timed_out);
+
+			match kind {
+				WagerKind::Toss { payout, .. } | WagerKind::Roll { payout, .. } =>
+					Self::release(payout),
+				WagerKind::Batch { guesses, head_probability, stake } =>
+					Self::release(Self::batch_payout_for(stake, head_probability, &guesses)),
+				WagerKind::DoubleOrNothing { stake, payout, length, .. } => {
+					StreakTosses::<T>::remove(&who);
+					if length.is_zero() {
+						Self::release(payout);
+					} else {
+						Self::release(payout.saturating_sub(stake));
+						Self::settle_streak(&who, stake)?;
+					}
+				},
+				WagerKind::AssetToss { asset, stake, payout, .. } => {
+					AssetCommitted::<T>::mutate(asset, |committed| {
+						*committed = committed.saturating_sub(payout)
+					});
+					T::Assets::transfer(
+						asset,
+						&Self::account_id(),
+						&who,
+						stake,
+						Preservation::Expendable,
+					)?;
+					if let Some(limits) = AssetBetLimits::<T>::get(asset) {
+						Self::note_winnings(&who, Self::native_value(&limits, stake));
+					}
+				},
+				WagerKind::SeriesToss => {
+					SeriesTosses::<T>::mutate_exists(&who, |pending| {
+						if *pending == Some(toss_id) {
+							*pending = None;
+						}
+					});
+				},
+				WagerKind::GlobalRound { .. } => {},
+			}
+
+			Self::deposit_event(Event::WagerRefunded { toss_id, who, round });
+			Ok(())
+		}
+
+		fn commit(amount: BalanceOf<T>) {
+			Committed::<T>::mutate(|committed| *committed = committed.saturating_add(amount));
+		}
+
+		fn release(amount: BalanceOf<T>) {
+			Committed::<T>::mutate(|committed| *committed = committed.saturating_sub(amount));
+		}
+
+		fn settle_expired_streaks(n: BlockNumberFor<T>) -> Weight {
+			let expiring = StreakExpiries::<T>::take(n);
+			let count = expiring.len() as u64;
+
+			for who in expiring {
+				if let Some(streak) = Streaks::<T>::take(&who) {
+					if let Err(e) = Self::settle_streak(&who, streak.pending) {
+						log::error!(target: LOG_TARGET, "Expired streak not settled: {:?}", e);
+						// Keep the streak open
+						Self::commit(streak.pending);
+						Streaks::<T>::insert(&who, streak);
+					}
+				}
+			}
+
+			T::DbWeight::get().reads_writes(1 + 2 * count, 1 + 3 * count)
+		}
+
+		/// Global round accepting players at block `n`.
+		pub fn round_at(n: BlockNumberFor<T>) -> u32 {
+			let length = T::RoundLength::get().max(One::one());
+			(n / length).saturated_into()
+		}
+
+		/// Resolve the global round `round` on the side the coin landed on in the toss `toss_id`.
+		fn resolve_round(
+			toss_id: TossId,
+			round: u32,
+			result: CoinSide,
+			randomness: RandomnessSource<BlockNumberFor<T>>,
+		) {
+			// Entries whose escrow was refunded no longer take part
+			let entries: Vec<_> = RoundEntries::<T>::take(round)
+				.into_iter()
+				.filter_map(|(who, guess, _)| {
+					let stake = Self::settle_escrow(&EscrowPurpose::Round(round), &who);
+					(!stake.is_zero()).then_some((who, guess, stake))
+				})
+				.collect();
+
+			let (winners, winners_pool, losers_pool) = entries.iter().fold(
+				(0u32, BalanceOf::<T>::zero(), BalanceOf::<T>::zero()),
+				|(winners, winners_pool, losers_pool), (_, guess, stake)| {
+					if *guess == result {
+						(winners + 1, winners_pool.saturating_add(*stake), losers_pool)
+					} else {
+						(winners, winners_pool, losers_pool.saturating_add(*stake))
+					}
+				},
+			);
+
+			// Without winners every stake is refunded
+			let fee =
+				if winners == 0 { Zero::zero() } else { T::RoundFee::get().mul_floor(losers_pool) };
+			let distributable = losers_pool.saturating_sub(fee);
+
+			for (who, guess, stake) in entries {
+				let amount = if winners == 0 {
+					stake
+				} else if guess == result {
+					stake.saturating_add(Self::pro_rata(distributable, stake, winners_pool))
+				} else {
+					continue
+				};
+
+				match Self::pay_out(&who, amount) {
+					Ok(()) => Self::deposit_event(Event::RoundPaidOut { who, round, amount }),
+					Err(e) => log::error!(target: LOG_TARGET, "Round payout failed: {:?}", e),
+				}
+			}
+
+			let round_result = RoundResult { result, winners, winners_pool, losers_pool, fee };
+			RoundResults::<T>::insert(round, round_result.clone());
+			Self::deposit_event(Event::RoundResolved {
+				toss_id,
+				round,
+				result: round_result,
+				randomness,
+			});
+		}
+
+		/// Lottery draw selling tickets at block `n`.
+		pub fn lottery_draw_at(n: BlockNumberFor<T>) -> u32 {
+			let period = T::LotteryPeriod::get().max(One::one());
+			(n / period).saturated_into()
+		}
+
+		/// Draw the winner of the lottery ending at block `n`, if any. The pot rolls over to the
+		/// next draw when no ticket was sold or the prize cannot be paid.
+		fn draw_lottery(n: BlockNumberFor<T>) -> Weight {
+			let period = T::LotteryPeriod::get().max(One::one());
+			if n.is_zero() || !(n % period).is_zero() {
+				return Weight::zero()
+			}
+
+			let draw = Self::lottery_draw_at(n).saturating_sub(1);
+			let tickets = LotteryTickets::<T>::take(draw);
+			let pot = LotteryPot::<T>::get();
+
+			if tickets.is_empty() {
+				Self::deposit_event(Event::LotteryRolledOver { draw, pot });
+				return T::DbWeight::get().reads_writes(2, 1)
+			}
+
+			// Every ticket is read to draw the winner
+			let per_ticket =
+				Weight::from_parts(LOTTERY_TICKET_REF_TIME, T::AccountId::max_encoded_len() as u64);
+			let weight = T::DbWeight::get()
+				.reads_writes(3, 5)
+				.saturating_add(per_ticket.saturating_mul(tickets.len() as u64))
+				.saturating_add(Self::pay_out_weight());
+
+			let (random_hash, randomness) = Self::block_random_hash(&(b"lottery", draw));
+			let index = Self::reduce_random_hash(random_hash, tickets.len() as u32);
+			let winner = tickets[index as usize].clone();
+
+			let house_cut = T::LotteryHouseCut::get().mul_floor(pot);
+			let prize = pot.saturating_sub(house_cut);
+
+			Self::release(pot);
+			match Self::pay_out(&winner, prize) {
+				Ok(()) => {
+					let result =
+						LotteryResult { winner, tickets: tickets.len() as u32, prize, house_cut };
+					LotteryPot::<T>::kill();
+					LotteryResults::<T>::insert(draw, result.clone());
+					Self::deposit_event(Event::LotteryDrawn { draw, result, randomness });
+				},
+				Err(e) => {
+					log::error!(target: LOG_TARGET, "Lottery prize not paid: {:?}", e);
+					Self::commit(pot);
+					Self::deposit_event(Event::LotteryRolledOver { draw, pot });
+				},
+			}
+
+			weight
+		}
+
+		/// Start tournaments reaching their start block and play the next bracket round of the
+		/// ones in progress.
+		fn advance_tournaments(n: BlockNumberFor<T>) -> Weight {
+			let mut weight = T::DbWeight::get().reads(1);
+			let mut active = ActiveTournaments::<T>::get();
+			let count = active.len();
+
+			active.retain(|id| {
+				let Some(mut tournament) = Tournaments::<T>::get(id) else { return false };
+				weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+
+				weight.saturating_accrue(match tournament.status.clone() {
+					TournamentStatus::Registration if n >= tournament.start_block =>
+						Self::start_tournament(*id, &mut tournament),
+					TournamentStatus::InProgress { round } =>
+						Self::play_tournament_round(*id, round, &mut tournament),
+					_ => Weight::zero(),
+				});
+
+				let still_active = matches!(
+					tournament.status,
+					TournamentStatus::Registration | TournamentStatus::InProgress { .. }
+				);
+				Tournaments::<T>::insert(id, tournament);
+				still_active
+			});
+
+			if active.len() != count {
+				ActiveTournaments::<T>::put(active);
+			}
+
+			weight
+		}
+
+		/// Shuffle the registered players into a bracket, moving their entry fees to the house, or
+		/// release the entry fees when fewer than two registered. Returns the weight used, which
+		/// grows with the number of players as each escrow is settled.
+		fn start_tournament(
+			id: TournamentId,
+			tournament: &mut Tournament<AccountIdOf<T>, BalanceOf<T>, BlockNumberFor<T>>,
+		) -> Weight {
+			let mut players = TournamentPlayers::<T>::get(id).into_inner();
+			// The escrow, the holds and the account of every player, and the house account
+			let per_player = T::DbWeight::get().reads_writes(4, 4);
+			let weight = T::DbWeight::get()
+				.reads_writes(1, 1)
+				.saturating_add(per_player.saturating_mul(players.len() as u64));
+
+			if players.len() < 2 {
+				Self::cancel_registration(id, tournament);
+				return weight
+			}
+			Self::release_tournament_deposit(tournament);
+
+			// Fisher-Yates shuffle
+			let seed = Self::random_hash(&(b"tournament", id));
+			for i in (1..players.len()).rev() {
+				let random_hash = T::Hashing::hash_of(&(seed, i as u32));
+				let j = Self::reduce_random_hash(random_hash, i as u32 + 1) as usize;
+				players.swap(i, j);
+			}
+
+			let purpose = EscrowPurpose::Tournament(id);
+			tournament.prize_pool = players.iter().fold(Zero::zero(), |pool: BalanceOf<T>, player| {
+				pool.saturating_add(Self::settle_escrow(&purpose, player))
+			});
+			Self::commit(tournament.prize_pool);
+
+			let count = players.len() as u32;
+			TournamentPlayers::<T>::insert(id, BoundedVec::truncate_from(players));
+			tournament.status = TournamentStatus::InProgress { round: 0 };
+			Self::deposit_event(Event::TournamentStarted { id, players: count });
+
+			weight.saturating_add(T::DbWeight::get().reads_writes(1, 2))
+		}
+
+		/// Release the entry fees of the players registered for a tournament and the deposit of
+		/// its organizer, and cancel it.
+		fn cancel_registration(
+			id: TournamentId,
+			tournament: &mut Tournament<AccountIdOf<T>, BalanceOf<T>, BlockNumberFor<T>>,
+		) {
+			let purpose = EscrowPurpose::Tournament(id);
+			for player in TournamentPlayers::<T>::take(id) {
+				Self::release_escrow(&purpose, &player, false);
+			}
+			Self::release_tournament_deposit(tournament);
+
+			tournament.status = TournamentStatus::Cancelled;
+			tournament.prize_pool = Zero::zero();
+			Self::deposit_event(Event::TournamentCancelled { id });
+		}
+
+		fn release_tournament_deposit(
+			tournament: &mut Tournament<AccountIdOf<T>, BalanceOf<T>, BlockNumberFor<T>>,
+		) {
+			let deposit = sp_std::mem::replace(&mut tournament.deposit, Zero::zero());
+			if let Err(e) = T::NativeBalance::release(
+				&HoldReason::TournamentDeposit.into(),
+				&tournament.organizer,
+				deposit,
+				Precision::BestEffort,
+			) {
+				log::error!(target: LOG_TARGET, "Tournament deposit not released: {:?}", e);
+			}
+		}
+
+		/// Pair the players of the bracket and flip a coin for every match. A random player of an
+		/// odd bracket advances on a bye. Returns the weight used.
+		fn play_tournament_round(
+			id: TournamentId,
+			round: u32,
+			tournament: &mut Tournament<AccountIdOf<T>, BalanceOf<T>, BlockNumberFor<T>>,
+		) -> Weight {
+			let mut players = TournamentPlayers::<T>::get(id).into_inner();
+			let (seed, randomness) = Self::block_random_hash(&(b"tournament", id, round));
+
+			if players.len() % 2 == 1 {
+				let random_hash = T::Hashing::hash_of(&(seed, b"bye"));
+				let bye = Self::reduce_random_hash(random_hash, players.len() as u32) as usize;
+				let last = players.len() - 1;
+				players.swap(bye, last);
+			}
+
+			let mut matches = BoundedVec::<Match<AccountIdOf<T>>, T::MaxTournamentPlayers>::new();
+			let mut survivors = BoundedVec::<AccountIdOf<T>, T::MaxTournamentPlayers>::new();
+			for (index, pair) in players.chunks(2).enumerate() {
+				let first = pair[0].clone();
+				let second = pair.get(1).cloned();
+				let winner = match &second {
+					Some(second) => {
+						let random_hash = T::Hashing::hash_of(&(seed, index as u32));
+						if Self::reduce_random_hash(random_hash, COIN_SIDES) == 0 {
+							first.clone()
+						} else {
+							second.clone()
+						}
+					},
+					None => first.clone(),
+				};
+
+				// Both vectors hold at most as many items as `players`
+				let _ = survivors.try_push(winner.clone());
+				let _ = matches.try_push(Match { first, second, winner });
+			}
+
+			let finalists = matches.last().cloned();
+			TournamentMatches::<T>::insert(id, round, matches);
+			TournamentPlayers::<T>::insert(id, survivors.clone());
+			Self::deposit_event(Event::TournamentRoundPlayed { id, round, randomness });
+
+			let weight = T::DbWeight::get().reads_writes(1, 2);
+			match (survivors.len(), finalists) {
+				(1, Some(Match { first, second: Some(second), winner })) => {
+					let runner_up = if winner == first { second } else { first };
+					Self::finish_tournament(id, tournament, winner, runner_up);
+					weight.saturating_add(Self::pay_out_weight().saturating_mul(2))
+				},
+				_ => {
+					tournament.status = TournamentStatus::InProgress { round: round + 1 };
+					weight
+				},
+			}
+		}
+
+		fn finish_tournament(
+			id: TournamentId,
+			tournament: &mut Tournament<AccountIdOf<T>, BalanceOf<T>, BlockNumberFor<T>>,
+			champion: AccountIdOf<T>,
+			runner_up: AccountIdOf<T>,
+		) {
+			let champion_prize = T::TournamentWinnerShare::get().mul_floor(tournament.prize_pool);
+			let runner_up_prize = tournament.prize_pool.saturating_sub(champion_prize);
+			Self::release(tournament.prize_pool);
+
+			for (player, prize) in [(&champion, champion_prize), (&runner_up, runner_up_prize)] {
+				if let Err(e) = Self::pay_out(player, prize) {
+					log::error!(target: LOG_TARGET, "Tournament prize not paid: {:?}", e);
+				}
+			}
+
+			tournament.status = TournamentStatus::Finished;
+			Self::deposit_event(Event::TournamentFinished {
+				id,
+				champion,
+				champion_prize,
+				runner_up,
+				runner_up_prize,
+			});
+		}
+
+		/// `amount * part / total`, rounded down.
+		fn pro_rata(amount: BalanceOf<T>, part: BalanceOf<T>, total: BalanceOf<T>) -> BalanceOf<T> {
+			multiply_by_rational_with_rounding(
+				amount.saturated_into(),
+				part.saturated_into(),
+				total.saturated_into(),
+				Rounding::Down,
+			)
+			.map(|share| share.saturated_into())
+			.unwrap_or_else(Zero::zero)
+		}
+
+		fn settle_streak(who: &T::AccountId, pending: BalanceOf<T>) -> DispatchResult {
+			Self::release(pending);
+			Self::pay_out(who, pending)?;
+			Self::deposit_event(Event::StreakSettled { who: who.clone(), amount: pending });
+			Ok(())
+		}
+
+		fn unschedule_streak_expiry(who: &T::AccountId, expires_at: BlockNumberFor<T>) {
+			StreakExpiries::<T>::mutate(expires_at, |accounts| accounts.retain(|a| a != who));
+		}
+
+		fn deposit_toss_event(
+			toss_id: TossId,
+			who: T::AccountId,
+			guess: CoinSide,
+			result: CoinSide,
+			stake: BalanceOf<T>,
+			payout: BalanceOf<T>,
+			randomness: RandomnessSource<BlockNumberFor<T>>,
+		) {
+			let event = if guess == result {
+				Event::CoinGuessed { toss_id, who, guess, result, stake, payout, randomness }
+			} else {
+				Event::CoinNotGuessed { toss_id, who, guess, result, stake, randomness }
+			};
+			Self::deposit_event(event);
+		}
+
+		/// Turn the coin of `who`, if it still has one, to the side of a resolved toss.
+		fn flip_coin(who: T::AccountId, toss_result: CoinSide) {
+			let Ok(coin) = Self::get_coin(&who) else { return };
+			if coin.side != toss_result {
+				Self::mutate_coin(&who, Coin { side: toss_result.clone(), ..coin });
+				Self::deposit_event(Event::CoinFlipped { who, side: toss_result });
+			}
+		}
+
+		/// Beacon round being published at the current time.
+		pub fn current_beacon_round() -> u64 {
+			Self::beacon_round_at_time(T::UnixTime::now().as_secs())
+		}
+
+		/// Beacon round being published when the block `n` is expected, the inverse of
+		/// `beacon_round_block`.
+		pub fn beacon_round_at(n: BlockNumberFor<T>) -> u64 {
+			let now = T::UnixTime::now().as_millis().saturated_into::<u64>();
+			let blocks: u64 =
+				n.saturating_sub(frame_system::Pallet::<T>::block_number()).saturated_into();
+			let expected_at = now.saturating_add(blocks.saturating_mul(T::ExpectedBlockTime::get()));
+
+			Self::beacon_round_at_time(expected_at / 1_000)
+		}
+
+		/// Beacon round being published at the unix time `secs`.
+		fn beacon_round_at_time(secs: u64) -> u64 {
+			let genesis = T::BeaconGenesisTime::get();
+			if secs < genesis {
+				return 0
+			}
+
+			(secs - genesis) / T::BeaconPeriod::get().max(1) + 1
+		}
+
+		/// Beacon round resolving the wagers placed now: `BeaconRoundDelay` rounds after the one
+		/// being published, so that nobody knows its randomness yet, and never a round already
+		/// imported.
+		pub fn next_wager_round() -> u64 {
+			Self::current_beacon_round()
+				.saturating_add(T::BeaconRoundDelay::get())
+				.max(LatestBeaconRound::<T>::get().saturating_add(1))
+		}
+
+		/// Queue a wager of `who` on the next beacon round that can resolve it.
+		fn queue_wager(toss_id: TossId, who: &T::AccountId, kind: WagerKind<T>) -> DispatchResult {
+			Self::queue_wager_on(Self::next_wager_round(), toss_id, who, kind)
+		}
+
+		/// Queue a wager of `who` on the beacon round `round`, holding its stake in escrow. The
+		/// wagers of the players are refunded `EscrowTimeout` blocks after the beacon round is
+		/// expected if it was not imported by then.
+		fn queue_wager_on(
+			round: u64,
+			toss_id: TossId,
+			who: &T::AccountId,
+			kind: WagerKind<T>,
+		) -> DispatchResult {
+			let escrowed = kind.escrowed();
+			let refundable = !matches!(kind, WagerKind::GlobalRound { .. });
+			BeaconWagers::<T>::try_mutate(round, |wagers| {
+				let tosses = wagers
+					.iter()
+					.fold(kind.tosses(), |tosses, wager| tosses.saturating_add(wager.kind.tosses()));
+				ensure!(tosses <= T::MaxBeaconTosses::get(), Error::<T>::TooManyBeaconWagers);
+				wagers
+					.try_push(BeaconWager { toss_id, who: who.clone(), kind })
+					.map_err(|_| Error::<T>::TooManyBeaconWagers)
+			})?;
+
+			if refundable {
+				let purpose = EscrowPurpose::Wager { round, toss_id };
+				let timeout_at =
+					Self::beacon_round_block(round).saturating_add(T::EscrowTimeout::get());
+				Self::schedule_escrow_timeout(timeout_at, &purpose, who)?;
+				if !escrowed.is_zero() {
+					Self::hold_escrow(purpose, who, escrowed, timeout_at)?;
+				}
+			}
+
+			Self::deposit_event(Event::WagerQueued { toss_id, who: who.clone(), round });
+			Ok(())
+		}
+
+		/// Block expected when the beacon publishes `round`, or the current block for a round
+		/// already published.
+		pub fn beacon_round_block(round: u64) -> BlockNumberFor<T> {
+			let now = T::UnixTime::now().as_millis().saturated_into::<u64>();
+			let published_at = T::BeaconGenesisTime::get()
+				.saturating_add(round.saturating_sub(1).saturating_mul(T::BeaconPeriod::get()))
+				.saturating_mul(1_000);
+			let block_time = T::ExpectedBlockTime::get().max(1);
+			let blocks = published_at.saturating_sub(now).saturating_add(block_time - 1) / block_time;
+
+			frame_system::Pallet::<T>::block_number().saturating_add(blocks.saturated_into())
+		}
+
+		/// Outcome in `0..sides` of the toss `toss_id` resolved with the randomness of a beacon
+		/// round. Every toss of a round gets its own outcome.
+		pub fn beacon_outcome(randomness: &[u8; 32], toss_id: TossId, sides: u32) -> u32 {
+			Self::reduce_random_hash(T::Hashing::hash_of(&(randomness, toss_id)), sides)
+		}
+
+		/// Side a coin with the given bias lands on for the toss `toss_id` resolved with the
+		/// randomness of a beacon round.
+		pub fn beacon_coin_side(
+			randomness: &[u8; 32],
+			toss_id: TossId,
+			head_probability: Permill,
+		) -> CoinSide {
+			let outcome = Self::beacon_outcome(randomness, toss_id, Permill::ACCURACY);
+			CoinSide::from_biased_outcome(outcome, head_probability)
+		}
+
+		pub fn verify_beacon_pulse(pulse: &BeaconPulse) -> bool {
+			pulse.randomness_matches_signature() && T::BeaconVerifier::verify(pulse)
+		}
+
+		/// Resolve the wagers waiting on a verified beacon round with its randomness. The
+		/// randomness is not kept once consumed, it is found in the import event.
+		pub fn do_import_beacon_pulse(pulse: BeaconPulse) -> DispatchResultWithPostInfo {
+			ensure!(BeaconWagers::<T>::contains_key(pulse.round), Error::<T>::BeaconRoundNotAwaited);
+			ensure!(Self::verify_beacon_pulse(&pulse), Error::<T>::InvalidBeaconPulse);
+
+			LatestBeaconRound::<T>::mutate(|latest| *latest = (*latest).max(pulse.round));
+			Self::deposit_event(Event::BeaconPulseImported {
+				round: pulse.round,
+				randomness: pulse.randomness,
+			});
+
+			let mut tosses = 0u32;
+			for wager in BeaconWagers::<T>::take(pulse.round) {
+				let toss_id = wager.toss_id;
+				tosses = tosses.saturating_add(wager.kind.tosses());
+				// A failing wager leaves no partial state, does not hold back the others and is
+				// refunded
+				let resolved = with_storage_layer(|| {
+					Self::resolve_wager(pulse.round, &pulse.randomness, wager.clone())
+				});
+				if let Err(e) = resolved {
+					log::error!(target: LOG_TARGET, "Wager {} not resolved: {:?}", toss_id, e);
+					let refunded =
+						with_storage_layer(|| Self::refund_wager(pulse.round, wager, false));
+					if let Err(e) = refunded {
+						log::error!(target: LOG_TARGET, "Wager {} not refunded: {:?}", toss_id, e);
+					}
+				}
+			}
+
+			Ok(Some(T::WeightInfo::submit_beacon_pulse(tosses)).into())
+		}
+
+		/// Resolve a wager with the randomness of the beacon round it waited for.
+		fn resolve_wager(
+			round: u64,
+			randomness: &[u8; 32],
+			wager: BeaconWager<T>,
+		) -> DispatchResult {
+			let BeaconWager { toss_id, who, kind } = wager;
+			let source = RandomnessSource::Beacon { round };
+			let escrowed = kind.escrowed();
+			ensure!(
+				Self::settle_escrow(&EscrowPurpose::Wager { round, toss_id }, &who) >= escrowed,
+				Error::<T>::EscrowNotSettled
+			);
+
+			match kind {
+				WagerKind::Toss { guess, head_probability, stake, payout } => {
+					let result = Self::beacon_coin_side(randomness, toss_id, head_probability);
+					let won = guess == result;
+					let paid_out = Self::settle_wager(&who, payout, won)?;
+					Self::note_jackpot_toss(&who, stake, won)?;
+					Self::deposit_toss_event(
+						toss_id,
+						who.clone(),
+						guess,
+						result.clone(),
+						stake,
+						paid_out,
+						source,
+					);
+					Self::flip_coin(who, result);
+				},
+				WagerKind::Batch { guesses, head_probability, stake } => {
+					let max_payout = Self::batch_payout_for(stake, head_probability, &guesses);
+					Self::release(max_payout);
+
+					let mut won = 0u32;
+					let mut paid_out = BalanceOf::<T>::zero();
+					let mut last_result = None;
+					for (index, guess) in guesses.iter().enumerate() {
+						let id = toss_id.wrapping_add(index as TossId);
+						let result = Self::beacon_coin_side(randomness, id, head_probability);
+						let guessed = *guess == result;
+						if guessed {
+							won += 1;
+							paid_out = paid_out.saturating_add(Self::coin_payout_for(
+								stake,
+								head_probability,
+								guess,
+							));
+						}
+						Self::note_jackpot_toss(&who, stake, guessed)?;
+						last_result = Some(result);
+					}
+					Self::pay_out(&who, paid_out)?;
+
+					if let (Ok(coin), Some(side)) = (Self::get_coin(&who), last_result) {
+						Self::mutate_coin(&who, Coin { side, ..coin });
+					}
+					let tosses = guesses.len() as u32;
+					Self::deposit_event(Event::TossesBatched {
+						who,
+						first_toss_id: toss_id,
+						tosses,
+						won,
+						staked: stake.saturating_mul(tosses.into()),
+						paid_out,
+					});
+				},
+				WagerKind::Roll { sides, guess, stake, payout } => {
+					let result = Self::beacon_outcome(randomness, toss_id, sides) + 1;
+					let won = guess == result;
+					let payout = Self::settle_wager(&who, payout, won)?;
+					if !won {
+						Self::feed_jackpot(stake);
+					}
+					Self::deposit_event(Event::DiceRolled {
+						toss_id,
+						who,
+						sides,
+						guess,
+						result,
+						stake,
+						payout,
+						randomness: source,
+					});
+				},
+				WagerKind::DoubleOrNothing { guess, head_probability, stake, payout, length } => {
+					StreakTosses::<T>::remove(&who);
+					let result = Self::beacon_coin_side(randomness, toss_id, head_probability);
+					let won = guess == result;
+					Self::deposit_toss_event(
+						toss_id,
+						who.clone(),
+						guess,
+						result.clone(),
+						stake,
+						if won { payout } else { Zero::zero() },
+						source,
+					);
+					Self::flip_coin(who.clone(), result);
+
+					if !won {
+						Self::release(payout);
+						return Ok(())
+					}
+					Self::win_streak(&who, payout, length.saturating_add(1))?;
+				},
+				WagerKind::AssetToss { asset, guess, head_probability, stake, payout } => {
+					let result = Self::beacon_coin_side(randomness, toss_id, head_probability);
+					let payout = Self::settle_asset_wager(asset, &who, payout, guess == result)?;
+					Self::deposit_event(Event::AssetCoinTossed {
+						toss_id,
+						who: who.clone(),
+						asset,
+						guess,
+						result: result.clone(),
+						stake,
+						payout,
+						randomness: source,
+					});
+					Self::flip_coin(who, result);
+				},
+				WagerKind::SeriesToss => {
+					// The toss of a series that expired in the meantime counts for no other
+					ensure!(
+						SeriesTosses::<T>::get(&who) == Some(toss_id),
+						Error::<T>::SeriesNotFound
+					);
+					SeriesTosses::<T>::remove(&who);
+					let result = CoinSide::from_outcome(Self::beacon_outcome(
+						randomness,
+						toss_id,
+						COIN_SIDES,
+					));
+					Self::record_series_toss(toss_id, who, result, source)?;
+				},
+				WagerKind::GlobalRound { round } => {
+					let result = CoinSide::from_outcome(Self::beacon_outcome(
+						randomness,
+						toss_id,
+						COIN_SIDES,
+					));
+					Self::resolve_round(toss_id, round, result, source);
+				},
+			}
+
+			Ok(())
+		}
+
+		/// Count the toss `toss_id` of the open series of `who`, resolving the series once a side
+		/// reaches the majority.
+		fn record_series_toss(
+			toss_id: TossId,
+			who: T::AccountId,
+			result: CoinSide,
+			randomness: RandomnessSource<BlockNumberFor<T>>,
+		) -> DispatchResult {
+			let mut series = OpenSeries::<T>::get(&who).ok_or(Error::<T>::SeriesNotFound)?;
+
+			match result {
+				CoinSide::Head => series.heads += 1,
+				CoinSide::Tail => series.tails += 1,
+			}
+			Self::deposit_event(Event::SeriesTossed {
+				toss_id,
+				who: who.clone(),
+				result,
+				heads: series.heads,
+				tails: series.tails,
+				randomness,
+			});
+
+			match series.winner() {
+				None => OpenSeries::<T>::insert(&who, series),
+				Some(winner) => {
+					OpenSeries::<T>::remove(&who);
+					ensure!(
+						Self::settle_escrow(&EscrowPurpose::Series, &who) >= series.stake,
+						Error::<T>::EscrowNotSettled
+					);
+					let payout = Self::payout_for(series.stake, COIN_SIDES);
+					Self::release(payout);
+					if winner == series.guess {
+						Self::pay_out(&who, payout)?;
+						Self::deposit_event(Event::SeriesWon { who, payout });
+					} else {
+						Self::feed_jackpot(series.stake);
+						Self::deposit_event(Event::SeriesLost { who, stake: series.stake });
+					}
+				},
+			}
+
+			Ok(())
+		}
+
+		/// Keep the committed winnings of a streak that won `length` tosses in a row pending, or
+		/// pay them out once the streak reaches `MaxStreakLength` or cannot expire.
+		fn win_streak(who: &T::AccountId, pending: BalanceOf<T>, length: u32) -> DispatchResult {
+			if length >= T::MaxStreakLength::get() {
+				return Self::settle_streak(who, pending)
+			}
+
+			let expires_at =
+				frame_system::Pallet::<T>::block_number().saturating_add(T::StreakExpiry::get());
+			let scheduled = StreakExpiries::<T>::try_mutate(expires_at, |accounts| {
+				accounts.try_push(who.clone())
+			});
+			if scheduled.is_err() {
+				return Self::settle_streak(who, pending)
+			}
+			Streaks::<T>::insert(who, Streak { pending, length, expires_at });
+
+			Self::deposit_event(Event::StreakWon { who: who.clone(), pending, length });
+			Ok(())
+		}
+
+		/// Fetch the earliest published beacon round that wagers wait on and submit it on chain.
+		/// Nothing is fetched while no wager waits on a published round. The transaction is
+		/// signed when the keystore holds a [`KEY_TYPE`] key and unsigned otherwise.
+		pub fn fetch_and_submit_beacon_pulse(
+			block_number: BlockNumberFor<T>,
+		) -> Result<(), &'static str> {
+			let current_round = Self::current_beacon_round();
+			let Some(pending_round) =
+				BeaconWagers::<T>::iter_keys().filter(|round| *round <= current_round).min()
+			else {
+				return Ok(())
+			};
+
+			let url = format!("{}/public/{}", T::BeaconEndpoint::get(), pending_round);
+			let pulse = Self::fetch_beacon_pulse(&url).map_err(|_| "failed to fetch beacon pulse")?;
+
+			if pulse.round != pending_round {
+				return Err("beacon returned an unexpected round")
+			}
+			if !Self::verify_beacon_pulse(&pulse) {
+				return Err("beacon pulse failed verification")
+			}
+
+			let last_submission = StorageValueRef::persistent(b"coin-game::last-beacon-submission");
+			if let Ok(Some((round, submitted_at))) =
+				last_submission.get::<(u64, BlockNumberFor<T>)>()
+			{
+				let resubmit_at = submitted_at.saturating_add(BEACON_RESUBMIT_INTERVAL.into());
+				if round == pulse.round && block_number < resubmit_at {
+					return Ok(())
+				}
+			}
+
+			let signer = Signer::<T, T::AuthorityId>::any_account();
+			if signer.can_sign() {
+				let (_, result) = signer
+					.send_signed_transaction(|_| Call::submit_beacon_pulse { pulse: pulse.clone() })
+					.ok_or("no local account available")?;
+				result.map_err(|()| "failed to submit signed beacon pulse")?;
+			} else {
+				let call = Call::submit_beacon_pulse_unsigned { pulse: pulse.clone() };
+				SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into())
+					.map_err(|()| "failed to submit unsigned beacon pulse")?;
+			}
+
+			last_submission.set(&(pulse.round, block_number));
+
+			Ok(())
+		}
+
+		fn fetch_beacon_pulse(url: &str) -> Result<BeaconPulse, http::Error> {
+			let deadline =
+				sp_io::offchain::timestamp().add(Duration::from_millis(BEACON_HTTP_TIMEOUT_MS));
+			let pending =
+				http::Request::get(url).deadline(deadline).send().map_err(|_| http::Error::IoError)?;
+			let response =
+				pending.try_wait(deadline).map_err(|_| http::Error::DeadlineReached)??;
+
+			if response.code != 200 {
+				log::warn!(target: LOG_TARGET, "Unexpected beacon status code: {}", response.code);
+				return Err(http::Error::Unknown)
+			}
+
+			let body = response.body().collect::<Vec<u8>>();
+			let body = sp_std::str::from_utf8(&body).map_err(|_| http::Error::Unknown)?;
+
+			beacon::parse_pulse(body).ok_or(http::Error::Unknown)
+		}
+
+		/// Random outcome in `0..sides` for the current block.
+		pub fn random_number(sides: u32) -> u32 {
+			let block_number = <frame_system::Pallet<T>>::block_number();
+			let seed = block_number.try_into().unwrap_or_else(|_| 0u32);
+
+			Self::generate_insecure_random_number(seed, sides)
+		}
+
+		/// Random hash for the current block, specific to the given subject.
+		pub fn random_hash(subject: &impl Encode) -> T::Hash {
+			Self::block_random_hash(subject).0
+		}
+
+		/// `T::Randomness` for `subject`, with the source it was queried from.
+		pub fn block_random_hash(
+			subject: &impl Encode,
+		) -> (T::Hash, RandomnessSource<BlockNumberFor<T>>) {
+			let subject = (T::PalletId::get(), subject).encode();
+			let (random_seed, _) = T::Randomness::random(&subject);
+			let block = frame_system::Pallet::<T>::block_number();
+			(random_seed, RandomnessSource::Block { block, subject })
+		}
+
+		pub fn generate_insecure_random_number(seed: u32, sides: u32) -> u32 {
+			let pallet_id = T::PalletId::get();
+			let (random_seed, _) = T::Randomness::random(&(pallet_id, seed).encode());
+			Self::reduce_random_hash(random_seed, sides)
+		}
+
+		/// Reduce a random hash to an outcome in `0..sides` without modulo bias.
+		///
+		/// The hash is read as little endian `u32` words, and words falling in the last, incomplete
+		/// multiple of `sides` are rejected. When every word of the hash is rejected the hash is
+		/// hashed again, up to `MAX_REHASHES` times.
+		pub fn reduce_random_hash(random_seed: T::Hash, sides: u32) -> u32 {
+			let sides = sides.max(1);
+			let range = 1u64 << 32;
+			let limit = range - range % sides as u64;
+
+			let mut random_seed = random_seed;
+			for _ in 0..MAX_REHASHES {
+				for word in random_seed.as_ref().chunks_exact(4) {
+					let value = u32::from_le_bytes([word[0], word[1], word[2], word[3]]);
+					if (value as u64) < limit {
+						return value % sides
+					}
+				}
+				random_seed = T::Hashing::hash(random_seed.as_ref());
+			}
+
+			// Only reachable with a negligible probability, the bias left is not exploitable
+			let value = <u32>::decode(&mut random_seed.as_ref())
 				.expect("secure hashes should always be bigger than u32; qed");
-			random_number % 2 == 0
+			value % sides
 		}
 	}
 }
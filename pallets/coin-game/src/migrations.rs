@@ -0,0 +1,57 @@
+//! Storage migrations of the coin game pallet.
+
+use super::*;
+use frame_support::{
+	pallet_prelude::*,
+	traits::{GetStorageVersion, OnRuntimeUpgrade},
+	BoundedBTreeMap,
+};
+
+/// Migration to biased coins: every stored coin gains the probability of landing on `Head`.
+pub mod v1 {
+	use super::*;
+
+	/// Coin as stored before biased coins, always fair
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+	pub struct OldCoin {
+		pub side: CoinSide,
+	}
+
+	pub type OldCoins<T> = BoundedBTreeMap<
+		<T as frame_system::Config>::AccountId,
+		OldCoin,
+		ConstU32<COIN_STORAGE_LIMIT>,
+	>;
+
+	/// Turn every stored coin into a fair coin showing the same side.
+	pub struct MigrateToV1<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain = Pallet::<T>::on_chain_storage_version();
+			if on_chain >= 1 {
+				log::info!(target: LOG_TARGET, "Coin storage already at {:?}, skipping v1", on_chain);
+				return T::DbWeight::get().reads(1)
+			}
+
+			let translated = CoinStorage::<T>::translate::<OldCoins<T>, _>(|old| {
+				old.map(|coins| {
+					let mut migrated = BoundedBTreeMap::new();
+					for (who, coin) in coins {
+						// Both maps have the same bound
+						let _ = migrated.try_insert(who, Coin::fair(coin.side));
+					}
+					migrated
+				})
+			});
+			if translated.is_err() {
+				log::error!(target: LOG_TARGET, "Coin storage could not be decoded, left as is");
+			}
+
+			StorageVersion::new(1).put::<Pallet<T>>();
+			log::info!(target: LOG_TARGET, "Coin storage migrated to v1");
+
+			T::DbWeight::get().reads_writes(2, 2)
+		}
+	}
+}
@@ -1,20 +1,26 @@
 use crate as pallet_coin_game;
-use frame_support::{construct_runtime, parameter_types, traits::{ConstU16, ConstU32, ConstU64, Everything}, PalletId};
+use crate::beacon::{BeaconPulse, VerifyBeacon};
+use frame_support::{construct_runtime, parameter_types, traits::{AsEnsureOriginWithArg, ConstU16, ConstU32, ConstU64, Currency, Everything, OnUnbalanced, UnixTime, WithdrawReasons}, weights::constants::RocksDbWeight, PalletId};
 use frame_system::Config;
 use sp_core::H256;
 use sp_runtime::{
-	traits::{BlakeTwo256, IdentityLookup},
-	BuildStorage,
+	testing::{TestSignature, TestXt, UintAuthorityId},
+	traits::{BlakeTwo256, ConvertInto, Extrinsic as ExtrinsicT, IdentityLookup},
+	BuildStorage, Permill,
 };
 use frame_support_test::TestRandomness;
 
 type Block = frame_system::mocking::MockBlock<Test>;
+pub type Extrinsic = TestXt<RuntimeCall, ()>;
 
 
 construct_runtime!(
 	pub enum Test
 	{
 		System: frame_system,
+		Balances: pallet_balances,
+		Assets: pallet_assets,
+		Vesting: pallet_vesting,
 		CoinGame: pallet_coin_game,
 	}
 );
@@ -22,13 +28,41 @@ construct_runtime!(
 
 parameter_types! {
 	pub const CoinFlipperPalletId: PalletId = PalletId(*b"coinflip");
+	pub const BeaconEndpoint: &'static str = "https://api.drand.sh";
+	pub static Now: u64 = BEACON_GENESIS_TIME;
+	pub static TossCooldown: u64 = 0;
+	pub static CoinDeposit: u64 = 0;
+	pub static SolvencyBuffer: u64 = 0;
+	pub static CircuitBreakerThreshold: u64 = 0;
+	pub static TournamentDeposit: u64 = 0;
+	pub static RoundLength: u64 = 10;
+	pub const MaxBetFraction: Permill = Permill::from_percent(10);
+	pub static Rake: Permill = Permill::zero();
+	pub static JackpotContribution: Permill = Permill::zero();
+	pub static MaxTossesPerPeriod: u32 = 100;
+	pub const RoundFee: Permill = Permill::from_percent(10);
+	pub const TournamentWinnerShare: Permill = Permill::from_percent(70);
+	pub const LotteryHouseCut: Permill = Permill::from_percent(10);
+	pub UnvestedFundsAllowedWithdrawReasons: WithdrawReasons =
+		WithdrawReasons::except(WithdrawReasons::TRANSFER | WithdrawReasons::RESERVE);
 }
 
+pub const ALICE: u64 = 1;
+pub const BOB: u64 = 2;
+pub const CHARLIE: u64 = 3;
+pub const TREASURY: u64 = 99;
+pub const ASSET: u32 = 7;
+pub const INITIAL_BALANCE: u64 = 1_000;
+pub const HOUSE_FUNDS: u64 = 100_000;
+
+pub const BEACON_GENESIS_TIME: u64 = 1_595_431_050;
+pub const BEACON_PERIOD: u64 = 30;
+
 impl Config for Test {
 	type BaseCallFilter = Everything;
 	type BlockWeights = ();
 	type BlockLength = ();
-	type DbWeight = ();
+	type DbWeight = RocksDbWeight;
 	type RuntimeOrigin = RuntimeOrigin;
 	type RuntimeCall = RuntimeCall;
 	type Nonce = u64;
@@ -41,9 +75,9 @@ impl Config for Test {
 	type BlockHashCount = ConstU64<250>;
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
-	type OnKilledAccount = ();
+	type OnKilledAccount = CoinGame;
 	type SystemWeightInfo = ();
 	type SS58Prefix = ConstU16<42>;
 	type OnSetCode = ();
@@ -51,21 +85,238 @@ impl Config for Test {
 }
 
 
+impl pallet_balances::Config for Test {
+	type MaxLocks = ConstU32<10>;
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type RuntimeEvent = RuntimeEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+	type FreezeIdentifier = ();
+	type MaxFreezes = ();
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type MaxHolds = ConstU32<3>;
+}
+
+impl pallet_assets::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = u64;
+	type RemoveItemsLimit = ConstU32<1000>;
+	type AssetId = u32;
+	type AssetIdParameter = codec::Compact<u32>;
+	type Currency = Balances;
+	type CreateOrigin = AsEnsureOriginWithArg<frame_system::EnsureSigned<u64>>;
+	type ForceOrigin = frame_system::EnsureRoot<u64>;
+	type AssetDeposit = ConstU64<0>;
+	type AssetAccountDeposit = ConstU64<0>;
+	type MetadataDepositBase = ConstU64<0>;
+	type MetadataDepositPerByte = ConstU64<0>;
+	type ApprovalDeposit = ConstU64<0>;
+	type StringLimit = ConstU32<50>;
+	type Freezer = ();
+	type Extra = ();
+	type CallbackHandle = ();
+	type WeightInfo = ();
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
+}
+
+impl frame_system::offchain::SigningTypes for Test {
+	type Public = UintAuthorityId;
+	type Signature = TestSignature;
+}
+
+impl<LocalCall> frame_system::offchain::SendTransactionTypes<LocalCall> for Test
+where
+	RuntimeCall: From<LocalCall>,
+{
+	type OverarchingCall = RuntimeCall;
+	type Extrinsic = Extrinsic;
+}
+
+impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Test
+where
+	RuntimeCall: From<LocalCall>,
+{
+	fn create_transaction<C: frame_system::offchain::AppCrypto<Self::Public, Self::Signature>>(
+		call: RuntimeCall,
+		_public: UintAuthorityId,
+		_account: u64,
+		nonce: u64,
+	) -> Option<(RuntimeCall, <Extrinsic as ExtrinsicT>::SignaturePayload)> {
+		Some((call, (nonce, ())))
+	}
+}
+
+pub struct TestAuthId;
+
+impl frame_system::offchain::AppCrypto<UintAuthorityId, TestSignature> for TestAuthId {
+	type RuntimeAppPublic = UintAuthorityId;
+	type GenericSignature = TestSignature;
+	type GenericPublic = UintAuthorityId;
+}
+
+/// Accepts every pulse except the ones signed with an all-zero signature
+pub struct TestBeaconVerifier;
+
+impl VerifyBeacon for TestBeaconVerifier {
+	fn verify(pulse: &BeaconPulse) -> bool {
+		pulse.signature != [0u8; 96]
+	}
+}
+
+/// Creates the asset wagered in the benchmarks
+#[cfg(feature = "runtime-benchmarks")]
+pub struct AssetBenchmarkHelper;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_coin_game::BenchmarkHelper<u32, u64> for AssetBenchmarkHelper {
+	fn create_asset(owner: &u64) -> u32 {
+		use frame_support::traits::fungibles::Create;
+
+		let asset = ASSET + 1;
+		<Assets as Create<u64>>::create(asset, *owner, true, 1).expect("asset is not created yet");
+		asset
+	}
+}
+
+/// Deposits the rake into the `TREASURY` account
+pub struct RakeToTreasury;
+
+impl OnUnbalanced<pallet_balances::NegativeImbalance<Test>> for RakeToTreasury {
+	fn on_nonzero_unbalanced(amount: pallet_balances::NegativeImbalance<Test>) {
+		Balances::resolve_creating(&TREASURY, amount);
+	}
+}
+
+pub struct TestUnixTime;
+
+impl UnixTime for TestUnixTime {
+	fn now() -> core::time::Duration {
+		core::time::Duration::from_secs(Now::get())
+	}
+}
+
+impl pallet_vesting::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type BlockNumberToBalance = ConvertInto;
+	type MinVestedTransfer = ConstU64<1>;
+	type WeightInfo = ();
+	type UnvestedFundsAllowedWithdrawReasons = UnvestedFundsAllowedWithdrawReasons;
+	const MAX_VESTING_SCHEDULES: u32 = 3;
+}
+
 impl pallet_coin_game::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = ();
 	type PalletId = CoinFlipperPalletId;
 	type Randomness = TestRandomness<Self>;
+	type Currency = Balances;
+	type Assets = Assets;
+	type NativeBalance = Balances;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type Vesting = Vesting;
+	type CoinDeposit = CoinDeposit;
+	type CoinExpiry = ConstU64<20>;
+	type AdminOrigin = frame_system::EnsureRoot<u64>;
+	type MaxBetFraction = MaxBetFraction;
+	type SolvencyBuffer = SolvencyBuffer;
+	type CircuitBreakerThreshold = CircuitBreakerThreshold;
+	type Rake = Rake;
+	type RakeDestination = RakeToTreasury;
+	type LiquidityWithdrawalDelay = ConstU64<5>;
+	type LiquidityRedemptionWindow = ConstU64<10>;
+	type MinimumLiquidity = ConstU64<1_000>;
+	type EscrowTimeout = ConstU64<20>;
+	type MaxEscrowTimeouts = ConstU32<16>;
+	type MaxSides = ConstU32<20>;
+	type MaxBatch = ConstU32<8>;
+	type TossCooldown = TossCooldown;
+	type TossPeriod = ConstU64<10>;
+	type MaxTossesPerPeriod = MaxTossesPerPeriod;
+	type FreeTosses = ConstU32<2>;
+	type FreeTossPeriod = ConstU64<10>;
+	type DayLength = ConstU64<10>;
+	type LimitLooseningDelay = ConstU64<5>;
+	type MaxSeriesLength = ConstU32<5>;
+	type SeriesExpiry = ConstU64<50>;
+	type MaxStreakLength = ConstU32<3>;
+	type StreakExpiry = ConstU64<10>;
+	type MaxExpiringStreaks = ConstU32<4>;
+	type RoundLength = RoundLength;
+	type RoundFee = RoundFee;
+	type MaxRoundEntries = ConstU32<4>;
+	type MaxTournamentPlayers = ConstU32<4>;
+	type MaxActiveTournaments = ConstU32<2>;
+	type TournamentDeposit = TournamentDeposit;
+	type MaxRegistrationPeriod = ConstU64<20>;
+	type TournamentWinnerShare = TournamentWinnerShare;
+	type LotteryPeriod = ConstU64<10>;
+	type TicketPrice = ConstU64<10>;
+	type MaxTickets = ConstU32<8>;
+	type LotteryHouseCut = LotteryHouseCut;
+	type JackpotContribution = JackpotContribution;
+	type JackpotStreak = ConstU32<3>;
+	type MinJackpotStake = ConstU64<10>;
+	type AuthorityId = TestAuthId;
+	type BeaconVerifier = TestBeaconVerifier;
+	type UnixTime = TestUnixTime;
+	type BeaconEndpoint = BeaconEndpoint;
+	type BeaconGenesisTime = ConstU64<BEACON_GENESIS_TIME>;
+	type BeaconPeriod = ConstU64<BEACON_PERIOD>;
+	type BeaconRoundDelay = ConstU64<2>;
+	type ExpectedBlockTime = ConstU64<6_000>;
+	type MaxBeaconWagers = ConstU32<16>;
+	type MaxBeaconTosses = ConstU32<64>;
+	type BeaconUnsignedPriority = ConstU64<{ u64::MAX / 2 }>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = AssetBenchmarkHelper;
 }
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	let t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap().into();
+	let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![
+			(ALICE, INITIAL_BALANCE),
+			(BOB, INITIAL_BALANCE),
+			(CHARLIE, INITIAL_BALANCE),
+			(CoinGame::account_id(), HOUSE_FUNDS),
+		],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	pallet_assets::GenesisConfig::<Test> {
+		assets: vec![(ASSET, ALICE, true, 1)],
+		metadata: vec![],
+		accounts: vec![
+			(ASSET, ALICE, INITIAL_BALANCE),
+			(ASSET, BOB, INITIAL_BALANCE),
+			(ASSET, CoinGame::account_id(), HOUSE_FUNDS),
+		],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
     let mut ext = sp_io::TestExternalities::new(t);
     ext.execute_with(|| System::set_block_number(1));
     ext
 }
 
+// Build a beacon pulse whose randomness is derived from the given signature byte
+pub fn beacon_pulse(round: u64, signature_byte: u8) -> BeaconPulse {
+	let signature = [signature_byte; 96];
+	BeaconPulse {
+		round,
+		randomness: sp_io::hashing::sha2_256(&signature),
+		signature,
+		previous_signature: [1u8; 96],
+	}
+}
+
 // Helper to get the last events
 pub fn last_event() -> RuntimeEvent {
 	System::events().pop().expect("Event expected").event
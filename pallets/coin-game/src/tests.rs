@@ -1,10 +1,26 @@
-use crate::{mock::*, Event, Error, Coin, CoinSide};
-use frame_support::{assert_noop, assert_ok};
-use sp_runtime::DispatchError;
-
-// pub const ALICE: AccountId32 = AccountId32::new([1u8; 32]);
-const ALICE : u64 = 1;
-const BOB : u64 = 2;
+use crate::{mock::*, Event, Error, Coin, CoinSide, CoinStorage, AssetCommitted, BeaconWager, BeaconWagers, LatestBeaconRound, StreakTosses, WagerKind, OpenSeries, Series, SeriesTosses, Streak, StreakExpiries, Streaks,
+	RoundBeaconRounds, RoundEntries, RoundResult, RoundResults,
+	ActiveTournaments, Tournament, TournamentMatches, TournamentPlayers, TournamentStatus, Tournaments,
+	LotteryPot, LotteryResult, LotteryResults, LotteryTickets, NextTossId, RandomnessSource, CoinLastActivity, Committed, WageringPaused,
+	CheckCoinGame, TossActivities, TossActivity, FreeTossesUsed,
+	LiquidityShares, LiquidityWithdrawal, LiquidityWithdrawals, TotalShares, AssetBetLimits, BetLimits,
+	JackpotPot, JackpotStreaks, JackpotWin, JackpotWinCount, JackpotWins,
+	PendingPlayLimits, PendingPlayerLimits, PlayLimits, PlayerLimits, SelfExclusions, Claimable,
+	Escrow, EscrowPurpose, EscrowTimeouts, Escrows, HoldReason, PayoutVesting, PayoutVestingRule, VestingClaimable};
+use frame_support::traits::{fungible::InspectHold, Currency, ExistenceRequirement, Get, GetStorageVersion, Hooks, OnRuntimeUpgrade, StorageVersion, VestingSchedule};
+use frame_support::storage::unhashed;
+use crate::migrations::v1::{MigrateToV1, OldCoin, OldCoins};
+use sp_core::H256;
+use crate::beacon::{self, BeaconPulse, DrandChainedVerifier, VerifyBeacon};
+use crate::WeightInfo;
+use codec::{Decode, Encode};
+use frame_support::{assert_noop, assert_ok, dispatch::{DispatchResult, DispatchResultWithPostInfo, GetDispatchInfo, Pays}, traits::UnfilteredDispatchable, weights::{constants::RocksDbWeight, Weight}};
+use sp_core::offchain::{testing, OffchainDbExt, OffchainWorkerExt, TransactionPoolExt};
+use sp_runtime::{
+	traits::{BlakeTwo256, Hash, SignedExtension, ValidateUnsigned},
+	transaction_validity::{InvalidTransaction, TransactionSource, TransactionValidity},
+	DispatchError, FixedU128, PerThing, Permill, TokenError,
+};
 
 
 /// Call: create_coin
@@ -12,19 +28,19 @@ const BOB : u64 = 2;
 #[test]
 fn create_coin_works() {
 	new_test_ext().execute_with(|| {
-		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE)));
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
 
         let coin = CoinGame::get_coin(&ALICE).unwrap();
 
 		// Need to check side since it's random
         if coin.side == CoinSide::Head {
-            assert_eq!(coin, Coin { side: CoinSide::Head });
+            assert_eq!(coin, Coin::fair(CoinSide::Head));
         } else {
-            assert_eq!(coin, Coin { side: CoinSide::Tail });
+            assert_eq!(coin, Coin::fair(CoinSide::Tail));
 		}
 
 
-		let expected_event = RuntimeEvent::CoinGame(Event::CoinCreated(ALICE));
+		let expected_event = RuntimeEvent::CoinGame(Event::CoinCreated { who: ALICE });
 		assert_eq!(last_event(), expected_event);
 	});
 }
@@ -35,7 +51,7 @@ fn create_coin_works() {
 fn create_coin_fails_with_no_permission() {
 	new_test_ext().execute_with(|| {
 		assert_noop!(
-			CoinGame::create_coin(RuntimeOrigin::none()),
+			CoinGame::create_coin(RuntimeOrigin::none(), None),
 			DispatchError::BadOrigin
 		);
 	});
@@ -46,9 +62,9 @@ fn create_coin_fails_with_no_permission() {
 #[test]
 fn create_coin_fails_with_coin_already_exists() {
 	new_test_ext().execute_with(|| {
-		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE)));
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
 		assert_noop!(
-			CoinGame::create_coin(RuntimeOrigin::signed(ALICE)),
+			CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None),
 			Error::<Test>::CoinAlreadyExists
 		);
 	});
@@ -59,12 +75,12 @@ fn create_coin_fails_with_coin_already_exists() {
 #[test]
 fn get_coin_works() {
 	new_test_ext().execute_with(|| {
-		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE)));
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
 		let coin = CoinGame::get_coin(&ALICE).unwrap();
 		if coin.side == CoinSide::Head {
-			assert_eq!(coin, Coin { side: CoinSide::Head });
+			assert_eq!(coin, Coin::fair(CoinSide::Head));
 		} else {
-			assert_eq!(coin, Coin { side: CoinSide::Tail });
+			assert_eq!(coin, Coin::fair(CoinSide::Tail));
 		}
 	});
 }
@@ -81,3 +97,3172 @@ fn get_coin_fails_with_coin_not_found() {
 	});
 }
 
+
+fn to_hex(bytes: &[u8]) -> String {
+	bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn from_hex<const N: usize>(hex: &str) -> [u8; N] {
+	let mut bytes = [0u8; N];
+	for (byte, pair) in bytes.iter_mut().zip(hex.as_bytes().chunks(2)) {
+		*byte = u8::from_str_radix(std::str::from_utf8(pair).unwrap(), 16).unwrap();
+	}
+	bytes
+}
+
+fn beacon_response(pulse: &BeaconPulse) -> Vec<u8> {
+	format!(
+		r#"{{"round":{},"randomness":"{}","signature":"{}","previous_signature":"{}"}}"#,
+		pulse.round,
+		to_hex(&pulse.randomness),
+		to_hex(&pulse.signature),
+		to_hex(&pulse.previous_signature),
+	)
+	.into_bytes()
+}
+
+/// Whether a wager is won with the randomness of the beacon round it waits on
+fn wins(wager: &BeaconWager<Test>, randomness: &[u8; 32]) -> bool {
+	match &wager.kind {
+		WagerKind::Toss { guess, head_probability, .. } |
+		WagerKind::DoubleOrNothing { guess, head_probability, .. } |
+		WagerKind::AssetToss { guess, head_probability, .. } =>
+			CoinGame::beacon_coin_side(randomness, wager.toss_id, *head_probability) == *guess,
+		WagerKind::Roll { sides, guess, .. } =>
+			CoinGame::beacon_outcome(randomness, wager.toss_id, *sides) + 1 == *guess,
+		WagerKind::SeriesToss => {
+			let series = OpenSeries::<Test>::get(wager.who).expect("the series is open");
+			CoinSide::from_outcome(CoinGame::beacon_outcome(randomness, wager.toss_id, 2)) ==
+				series.guess
+		},
+		WagerKind::Batch { .. } => panic!("a batch has one outcome per toss"),
+		WagerKind::GlobalRound { .. } => panic!("a global round is settled with round_pulse"),
+	}
+}
+
+/// Import the earliest beacon round wagers wait on, with a randomness winning, or losing, all
+/// of them. Returns the randomness of the round.
+fn settle_wagers(won: bool) -> [u8; 32] {
+	let round = BeaconWagers::<Test>::iter_keys().min().expect("a wager waits on the beacon");
+	let wagers = BeaconWagers::<Test>::get(round);
+	let pulse = (1..=u8::MAX)
+		.map(|byte| beacon_pulse(round, byte))
+		.find(|pulse| wagers.iter().all(|wager| wins(wager, &pulse.randomness) == won))
+		.expect("some pulse settles the wagers");
+	assert_ok!(CoinGame::submit_beacon_pulse(RuntimeOrigin::signed(BOB), pulse.clone()));
+	pulse.randomness
+}
+
+/// Toss the coin of `who` on `Head` and settle the toss as won or lost
+fn toss(who: u64, stake: u64, won: bool) -> DispatchResultWithPostInfo {
+	let result = CoinGame::toss_coin(RuntimeOrigin::signed(who), CoinSide::Head, stake);
+	if result.is_ok() {
+		settle_wagers(won);
+	}
+	result
+}
+
+fn win_toss(who: u64, stake: u64) -> DispatchResultWithPostInfo {
+	toss(who, stake, true)
+}
+
+fn lose_toss(who: u64, stake: u64) -> DispatchResultWithPostInfo {
+	toss(who, stake, false)
+}
+
+/// Toss the coin of `who` on `Head` in double-or-nothing mode and settle the toss as won or lost
+fn double_or_nothing(who: u64, stake: Option<u64>, won: bool) -> DispatchResult {
+	let result = CoinGame::double_or_nothing(RuntimeOrigin::signed(who), CoinSide::Head, stake);
+	if result.is_ok() {
+		settle_wagers(won);
+	}
+	result
+}
+
+/// Call: toss_coin_with_beacon
+/// Happy path: test that the toss is queued against a future beacon round
+#[test]
+fn toss_coin_with_beacon_queues_toss() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+		assert_ok!(CoinGame::toss_coin_with_beacon(RuntimeOrigin::signed(ALICE), CoinSide::Head));
+
+		// Round 1 is being published at genesis and the mock delays wagers by two rounds
+		let wager = BeaconWager {
+			toss_id: 0,
+			who: ALICE,
+			kind: WagerKind::Toss {
+				guess: CoinSide::Head,
+				head_probability: Permill::from_percent(50),
+				stake: 0,
+				payout: 0,
+			},
+		};
+		assert_eq!(BeaconWagers::<Test>::get(3).into_inner(), vec![wager]);
+
+		let expected_event =
+			RuntimeEvent::CoinGame(Event::WagerQueued { toss_id: 0, who: ALICE, round: 3 });
+		assert_eq!(last_event(), expected_event);
+	});
+}
+
+/// Call: toss_coin_with_beacon
+/// Unhappy path: test that the toss fails when the coin does not exist
+#[test]
+fn toss_coin_with_beacon_fails_with_coin_not_found() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			CoinGame::toss_coin_with_beacon(RuntimeOrigin::signed(ALICE), CoinSide::Head),
+			Error::<Test>::CoinNotFound
+		);
+	});
+}
+
+/// Call: submit_beacon_pulse
+/// Happy path: test that importing a round resolves the queued tosses without keeping its
+/// randomness
+#[test]
+fn submit_beacon_pulse_resolves_queued_tosses() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+		assert_ok!(CoinGame::toss_coin_with_beacon(RuntimeOrigin::signed(ALICE), CoinSide::Head));
+
+		let pulse = beacon_pulse(3, 7);
+		assert_ok!(CoinGame::submit_beacon_pulse(RuntimeOrigin::signed(BOB), pulse.clone()));
+
+		assert_eq!(LatestBeaconRound::<Test>::get(), 3);
+		assert!(!BeaconWagers::<Test>::contains_key(3));
+		let expected_event =
+			RuntimeEvent::CoinGame(Event::BeaconPulseImported { round: 3, randomness: pulse.randomness });
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+
+		let result =
+			CoinGame::beacon_coin_side(&pulse.randomness, 0, Permill::from_percent(50));
+		let randomness = RandomnessSource::Beacon { round: 3 };
+		let expected_event = if result == CoinSide::Head {
+			RuntimeEvent::CoinGame(Event::CoinGuessed {
+				toss_id: 0,
+				who: ALICE,
+				guess: CoinSide::Head,
+				result: result.clone(),
+				stake: 0,
+				payout: 0,
+				randomness,
+			})
+		} else {
+			RuntimeEvent::CoinGame(Event::CoinNotGuessed {
+				toss_id: 0,
+				who: ALICE,
+				guess: CoinSide::Head,
+				result: result.clone(),
+				stake: 0,
+				randomness,
+			})
+		};
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+		assert_eq!(CoinGame::get_coin(&ALICE).unwrap().side, result);
+	});
+}
+
+/// Call: submit_beacon_pulse
+/// Unhappy path: test that pulses failing verification, no wager waits on or already imported
+/// are rejected
+#[test]
+fn submit_beacon_pulse_fails_with_invalid_pulse() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			CoinGame::submit_beacon_pulse(RuntimeOrigin::signed(BOB), beacon_pulse(3, 7)),
+			Error::<Test>::BeaconRoundNotAwaited
+		);
+
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+		assert_ok!(CoinGame::toss_coin_with_beacon(RuntimeOrigin::signed(ALICE), CoinSide::Head));
+		assert_noop!(
+			CoinGame::submit_beacon_pulse(RuntimeOrigin::signed(BOB), beacon_pulse(4, 7)),
+			Error::<Test>::BeaconRoundNotAwaited
+		);
+		assert_noop!(
+			CoinGame::submit_beacon_pulse(RuntimeOrigin::signed(BOB), beacon_pulse(3, 0)),
+			Error::<Test>::InvalidBeaconPulse
+		);
+
+		let mut tampered = beacon_pulse(3, 7);
+		tampered.randomness = [0u8; 32];
+		assert_noop!(
+			CoinGame::submit_beacon_pulse(RuntimeOrigin::signed(BOB), tampered),
+			Error::<Test>::InvalidBeaconPulse
+		);
+
+		assert_ok!(CoinGame::submit_beacon_pulse(RuntimeOrigin::signed(BOB), beacon_pulse(3, 7)));
+		assert_noop!(
+			CoinGame::submit_beacon_pulse(RuntimeOrigin::signed(BOB), beacon_pulse(3, 7)),
+			Error::<Test>::BeaconRoundNotAwaited
+		);
+	});
+}
+
+/// Call: submit_beacon_pulse
+/// Happy path: test that the import is charged for the most tosses a round can resolve and
+/// refunds the tosses it did not resolve
+#[test]
+fn submit_beacon_pulse_refunds_unused_weight() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+		assert_ok!(CoinGame::toss_coin_with_beacon(RuntimeOrigin::signed(ALICE), CoinSide::Head));
+		assert_ok!(CoinGame::toss_many(
+			RuntimeOrigin::signed(ALICE),
+			vec![CoinSide::Head; 3].try_into().unwrap(),
+			10
+		));
+
+		let call = crate::Call::<Test>::submit_beacon_pulse { pulse: beacon_pulse(3, 7) };
+		let charged = call.get_dispatch_info().weight;
+		assert_eq!(charged, <() as WeightInfo>::submit_beacon_pulse(64));
+
+		let post_info = call.dispatch_bypass_filter(RuntimeOrigin::signed(BOB)).unwrap();
+		assert_eq!(post_info.actual_weight, Some(<() as WeightInfo>::submit_beacon_pulse(4)));
+		assert!(post_info.actual_weight.unwrap().all_lt(charged));
+	});
+}
+
+/// Call: submit_beacon_pulse
+/// Happy path: test that a wager failing to resolve is refunded rather than dropped
+#[test]
+fn submit_beacon_pulse_refunds_wager_failing_to_resolve() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+		assert_ok!(CoinGame::toss_coin(RuntimeOrigin::signed(ALICE), CoinSide::Head, 10));
+		// The house can no longer pay the winnings out
+		Balances::make_free_balance_be(&CoinGame::account_id(), 5);
+
+		settle_wagers(true);
+
+		assert!(!BeaconWagers::<Test>::contains_key(3));
+		assert_eq!(Balances::balance_on_hold(&HoldReason::Escrow.into(), &ALICE), 0);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE);
+		assert_eq!(Balances::free_balance(CoinGame::account_id()), 5);
+		assert_eq!(Committed::<Test>::get(), 0);
+		assert_eq!(Claimable::<Test>::get(ALICE), 0);
+		let expected_event = RuntimeEvent::CoinGame(Event::EscrowReleased {
+			purpose: EscrowPurpose::Wager { round: 3, toss_id: 0 },
+			who: ALICE,
+			amount: 10,
+			timed_out: false,
+		});
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+		assert_eq!(
+			last_event(),
+			RuntimeEvent::CoinGame(Event::WagerRefunded { toss_id: 0, who: ALICE, round: 3 })
+		);
+	});
+}
+
+/// Call: toss_coin
+/// Unhappy path: test that no more tosses wait on a beacon round than its import can resolve
+#[test]
+fn toss_coin_fails_with_too_many_beacon_tosses() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+		// Eight batches of eight tosses fill the 64 tosses of round 3
+		for _ in 0..8 {
+			assert_ok!(CoinGame::toss_many(
+				RuntimeOrigin::signed(ALICE),
+				vec![CoinSide::Head; 8].try_into().unwrap(),
+				1
+			));
+		}
+
+		assert_noop!(
+			CoinGame::toss_coin(RuntimeOrigin::signed(ALICE), CoinSide::Head, 1),
+			Error::<Test>::TooManyBeaconWagers
+		);
+	});
+}
+
+/// Function: DrandChainedVerifier::verify
+/// Happy path: test that a pulse of the drand mainnet verifies against its public key, and that
+/// a tampered pulse does not
+#[test]
+fn drand_chained_verifier_accepts_mainnet_pulse() {
+	struct MainnetKey;
+	impl Get<[u8; 48]> for MainnetKey {
+		fn get() -> [u8; 48] {
+			from_hex("868f005eb8e6e4ca0a47c8a77ceaa5309a47978a7c71bc5cce96366b5d7a569937c529eeda66c7293784a9402801af31")
+		}
+	}
+
+	let pulse = beacon::parse_pulse(
+		r#"{"round":72785,"randomness":"8b676484b5fb1f37f9ec5c413d7d29883504e5b669f604a1ce68b3388e9ae3d9","signature":"82f5d3d2de4db19d40a6980e8aa37842a0e55d1df06bd68bddc8d60002e8e959eb9cfa368b3c1b77d18f02a54fe047b80f0989315f83b12a74fd8679c4f12aae86eaf6ab5690b34f1fddd50ee3cc6f6cdf59e95526d5a5d82aaa84fa6f181e42","previous_signature":"a609e19a03c2fcc559e8dae14900aaefe517cb55c840f6e69bc8e4f66c8d18e8a609685d9917efbfb0c37f058c2de88f13d297c7e19e0ab24813079efe57a182554ff054c7638153f9b26a60e7111f71a0ff63d9571704905d3ca6df0b031747"}"#,
+	)
+	.unwrap();
+	assert!(pulse.randomness_matches_signature());
+	assert!(DrandChainedVerifier::<MainnetKey>::verify(&pulse));
+
+	let tampered = BeaconPulse { round: pulse.round + 1, ..pulse.clone() };
+	assert!(!DrandChainedVerifier::<MainnetKey>::verify(&tampered));
+	let tampered = BeaconPulse { previous_signature: pulse.signature, ..pulse };
+	assert!(!DrandChainedVerifier::<MainnetKey>::verify(&tampered));
+}
+
+/// Function: validate_unsigned
+/// Unhappy path: test that invalid pulses, pulses no wager waits on and pulses gossiped by
+/// other nodes never reach the pool
+#[test]
+fn validate_unsigned_rejects_invalid_beacon_pulses() {
+	new_test_ext().execute_with(|| {
+		let call = crate::Call::submit_beacon_pulse_unsigned { pulse: beacon_pulse(3, 7) };
+		let expected: TransactionValidity = InvalidTransaction::Stale.into();
+		assert_eq!(CoinGame::validate_unsigned(TransactionSource::Local, &call), expected);
+
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+		assert_ok!(CoinGame::toss_coin_with_beacon(RuntimeOrigin::signed(ALICE), CoinSide::Head));
+		assert!(CoinGame::validate_unsigned(TransactionSource::Local, &call).is_ok());
+		assert!(CoinGame::validate_unsigned(TransactionSource::InBlock, &call).is_ok());
+		let expected: TransactionValidity = InvalidTransaction::Call.into();
+		assert_eq!(CoinGame::validate_unsigned(TransactionSource::External, &call), expected);
+
+		let bad_call = crate::Call::submit_beacon_pulse_unsigned { pulse: beacon_pulse(3, 0) };
+		let expected: TransactionValidity = InvalidTransaction::BadProof.into();
+		assert_eq!(CoinGame::validate_unsigned(TransactionSource::Local, &bad_call), expected);
+
+		assert_ok!(CoinGame::submit_beacon_pulse_unsigned(RuntimeOrigin::none(), beacon_pulse(3, 7)));
+		let expected: TransactionValidity = InvalidTransaction::Stale.into();
+		assert_eq!(CoinGame::validate_unsigned(TransactionSource::Local, &call), expected);
+	});
+}
+
+/// Function: fetch_and_submit_beacon_pulse
+/// Happy path: test that the off-chain worker fetches nothing while no wager waits on a
+/// published round
+#[test]
+fn offchain_worker_fetches_nothing_without_pending_wagers() {
+	let (offchain, _state) = testing::TestOffchainExt::new();
+	let (pool, pool_state) = testing::TestTransactionPoolExt::new();
+	let mut t = new_test_ext();
+	t.register_extension(OffchainWorkerExt::new(offchain.clone()));
+	t.register_extension(OffchainDbExt::new(offchain));
+	t.register_extension(TransactionPoolExt::new(pool));
+
+	t.execute_with(|| {
+		assert_ok!(CoinGame::fetch_and_submit_beacon_pulse(1));
+
+		// The round the toss waits on is not published yet
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+		assert_ok!(CoinGame::toss_coin_with_beacon(RuntimeOrigin::signed(ALICE), CoinSide::Head));
+		assert_ok!(CoinGame::fetch_and_submit_beacon_pulse(1));
+
+		assert!(pool_state.read().transactions.is_empty());
+	});
+}
+
+/// Function: fetch_and_submit_beacon_pulse
+/// Happy path: test that the off-chain worker fetches the round a queued toss is waiting on
+#[test]
+fn offchain_worker_fetches_pending_beacon_round() {
+	let (offchain, state) = testing::TestOffchainExt::new();
+	let (pool, pool_state) = testing::TestTransactionPoolExt::new();
+	let mut t = new_test_ext();
+	t.register_extension(OffchainWorkerExt::new(offchain.clone()));
+	t.register_extension(OffchainDbExt::new(offchain));
+	t.register_extension(TransactionPoolExt::new(pool));
+
+	let pulse = beacon_pulse(3, 7);
+	state.write().expect_request(testing::PendingRequest {
+		method: "GET".into(),
+		uri: "https://api.drand.sh/public/3".into(),
+		response: Some(beacon_response(&pulse)),
+		sent: true,
+		..Default::default()
+	});
+
+	t.execute_with(|| {
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+		assert_ok!(CoinGame::toss_coin_with_beacon(RuntimeOrigin::signed(ALICE), CoinSide::Head));
+
+		// Move the clock to the round the toss is waiting on
+		Now::set(BEACON_GENESIS_TIME + 2 * BEACON_PERIOD);
+		assert_ok!(CoinGame::fetch_and_submit_beacon_pulse(1));
+
+		let tx = pool_state.write().transactions.pop().unwrap();
+		let tx = Extrinsic::decode(&mut &*tx).unwrap();
+		assert_eq!(
+			tx.call,
+			RuntimeCall::CoinGame(crate::Call::submit_beacon_pulse_unsigned { pulse })
+		);
+	});
+}
+
+/// Call: roll
+/// Happy path: test that a winning guess is paid the stake times the number of sides once the
+/// beacon round of the roll is imported
+#[test]
+fn roll_pays_out_scaled_to_odds() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::roll(RuntimeOrigin::signed(ALICE), 6, 3, 10));
+
+		// The stake is in the house and the payout committed until the roll is resolved
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE - 10);
+		assert_eq!(Committed::<Test>::get(), 60);
+		assert_eq!(Claimable::<Test>::get(ALICE), 0);
+		assert_eq!(
+			last_event(),
+			RuntimeEvent::CoinGame(Event::WagerQueued { toss_id: 0, who: ALICE, round: 3 })
+		);
+
+		settle_wagers(true);
+
+		assert_eq!(Claimable::<Test>::get(ALICE), 60);
+		assert_eq!(CoinGame::available_bankroll(), HOUSE_FUNDS + 10 - 60);
+
+		let expected_event = RuntimeEvent::CoinGame(Event::DiceRolled {
+			toss_id: 0,
+			who: ALICE,
+			sides: 6,
+			guess: 3,
+			result: 3,
+			stake: 10,
+			payout: 60,
+			randomness: RandomnessSource::Beacon { round: 3 },
+		});
+		assert_eq!(last_event(), expected_event);
+	});
+}
+
+/// Call: roll
+/// Happy path: test that a losing guess leaves the stake to the house
+#[test]
+fn roll_keeps_stake_on_loss() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::roll(RuntimeOrigin::signed(ALICE), 20, 5, 10));
+		let randomness = settle_wagers(false);
+
+		let result = CoinGame::beacon_outcome(&randomness, 0, 20) + 1;
+		assert_ne!(result, 5);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE - 10);
+		assert_eq!(Balances::free_balance(CoinGame::account_id()), HOUSE_FUNDS + 10);
+		assert_eq!(Committed::<Test>::get(), 0);
+		let expected_event = RuntimeEvent::CoinGame(Event::DiceRolled {
+			toss_id: 0,
+			who: ALICE,
+			sides: 20,
+			guess: 5,
+			result,
+			stake: 10,
+			payout: 0,
+			randomness: RandomnessSource::Beacon { round: 3 },
+		});
+		assert_eq!(last_event(), expected_event);
+	});
+}
+
+/// Call: roll
+/// Unhappy path: test that the number of sides and the guess are checked
+#[test]
+fn roll_fails_with_invalid_sides_or_guess() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(CoinGame::roll(RuntimeOrigin::signed(ALICE), 1, 1, 10), Error::<Test>::InvalidSides);
+		assert_noop!(CoinGame::roll(RuntimeOrigin::signed(ALICE), 21, 1, 10), Error::<Test>::InvalidSides);
+		assert_noop!(CoinGame::roll(RuntimeOrigin::signed(ALICE), 6, 0, 10), Error::<Test>::InvalidGuess);
+		assert_noop!(CoinGame::roll(RuntimeOrigin::signed(ALICE), 6, 7, 10), Error::<Test>::InvalidGuess);
+	});
+}
+
+/// Call: roll, open_series, toss_series
+/// Unhappy path: test that dice rolls and series need a stake and count against the toss rate
+/// limit, so that they cannot fill a beacon round for free
+#[test]
+fn roll_and_series_fail_with_zero_stake_or_too_many_tosses() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(CoinGame::roll(RuntimeOrigin::signed(ALICE), 6, 1, 0), Error::<Test>::InvalidStake);
+		assert_noop!(
+			CoinGame::open_series(RuntimeOrigin::signed(ALICE), CoinSide::Head, 3, 0),
+			Error::<Test>::InvalidStake
+		);
+
+		MaxTossesPerPeriod::set(1);
+		assert_ok!(CoinGame::roll(RuntimeOrigin::signed(ALICE), 6, 1, 10));
+		assert_noop!(CoinGame::roll(RuntimeOrigin::signed(ALICE), 6, 1, 10), Error::<Test>::TooSoon);
+
+		assert_ok!(CoinGame::open_series(RuntimeOrigin::signed(BOB), CoinSide::Head, 3, 10));
+		assert_ok!(CoinGame::toss_series(RuntimeOrigin::signed(BOB)));
+		settle_wagers(false);
+		assert_noop!(CoinGame::toss_series(RuntimeOrigin::signed(BOB)), Error::<Test>::TooSoon);
+	});
+}
+
+/// Call: roll
+/// Unhappy path: test that a wager the house cannot cover is rejected
+#[test]
+fn roll_fails_with_insufficient_house_funds() {
+	new_test_ext().execute_with(|| {
+		let _ = Balances::make_free_balance_be(&CoinGame::account_id(), 10);
+		assert_noop!(
+			CoinGame::roll(RuntimeOrigin::signed(ALICE), 6, 1, 10),
+			Error::<Test>::InsufficientHouseFunds
+		);
+	});
+}
+
+/// Call: toss_coin
+/// Happy path: test that the coin mode is the two sided special case of a dice
+#[test]
+fn toss_coin_pays_out_even_odds() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+
+		assert_ok!(win_toss(ALICE, 10));
+
+		assert_eq!(Claimable::<Test>::get(ALICE), 20);
+		assert_eq!(CoinGame::get_coin(&ALICE).unwrap().side, CoinSide::Head);
+	});
+}
+
+/// Function: reduce_random_hash
+/// Happy path: test that words in the biased range are rejected instead of reduced
+#[test]
+fn reduce_random_hash_rejects_biased_words() {
+	new_test_ext().execute_with(|| {
+		// 2^32 is not a multiple of 6, so u32::MAX falls in the rejected range
+		let mut random_hash = [0u8; 32];
+		random_hash[..4].copy_from_slice(&u32::MAX.to_le_bytes());
+		random_hash[4..8].copy_from_slice(&7u32.to_le_bytes());
+
+		assert_eq!(CoinGame::reduce_random_hash(H256(random_hash), 6), 1);
+		// 2^32 is a multiple of 2, so nothing is rejected for a coin
+		assert_eq!(CoinGame::reduce_random_hash(H256(random_hash), 2), 1);
+	});
+}
+
+/// Call: create_coin
+/// Happy path: test that a coin can be minted with a bias
+#[test]
+fn create_coin_with_bias_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), Some(Permill::from_percent(60))));
+
+		let coin = CoinGame::get_coin(&ALICE).unwrap();
+		assert_eq!(coin.head_probability, Permill::from_percent(60));
+	});
+}
+
+/// Call: create_coin
+/// Unhappy path: test that a coin always landing on the same side is rejected
+#[test]
+fn create_coin_fails_with_invalid_bias() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			CoinGame::create_coin(RuntimeOrigin::signed(ALICE), Some(Permill::zero())),
+			Error::<Test>::InvalidBias
+		);
+		assert_noop!(
+			CoinGame::create_coin(RuntimeOrigin::signed(ALICE), Some(Permill::one())),
+			Error::<Test>::InvalidBias
+		);
+	});
+}
+
+/// Function: coin_payout_for
+/// Happy path: test that the payout of a biased coin never gives the player a positive
+/// expected value
+#[test]
+fn coin_payout_for_biased_coin_has_no_positive_expected_value() {
+	new_test_ext().execute_with(|| {
+		let bias = Permill::from_percent(60);
+
+		// 100 / 0.6 = 166.66.. is rounded down
+		assert_eq!(CoinGame::coin_payout_for(100, bias, &CoinSide::Head), 166);
+		assert_eq!(CoinGame::coin_payout_for(100, bias, &CoinSide::Tail), 250);
+
+		for stake in 1..1_000u64 {
+			for parts in [1, 3, 333_333, 500_000, 600_000, 999_999] {
+				let bias = Permill::from_parts(parts);
+				for (guess, probability) in
+					[(CoinSide::Head, parts as u128), (CoinSide::Tail, 1_000_000 - parts as u128)]
+				{
+					let payout = CoinGame::coin_payout_for(stake, bias, &guess) as u128;
+					assert!(payout * probability <= stake as u128 * 1_000_000);
+				}
+			}
+		}
+	});
+}
+
+/// Function: random_coin_side_for
+/// Happy path: test that the toss honours the bias of the coin
+#[test]
+fn random_coin_side_for_honours_bias() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(CoinSide::from_biased_outcome(599_999, Permill::from_percent(60)), CoinSide::Head);
+		assert_eq!(CoinSide::from_biased_outcome(600_000, Permill::from_percent(60)), CoinSide::Tail);
+
+		let outcome = CoinGame::random_number(1_000_000);
+		let head_coin = Coin { side: CoinSide::Tail, head_probability: Permill::from_parts(outcome + 1) };
+		let tail_coin = Coin { side: CoinSide::Head, head_probability: Permill::from_parts(outcome) };
+		assert_eq!(CoinGame::random_coin_side_for(&head_coin), CoinSide::Head);
+		assert_eq!(CoinGame::random_coin_side_for(&tail_coin), CoinSide::Tail);
+	});
+}
+
+/// Call: open_series
+/// Happy path: test that opening a series holds the stake once and stores the series
+#[test]
+fn open_series_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::open_series(RuntimeOrigin::signed(ALICE), CoinSide::Head, 3, 10));
+
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE - 10);
+		assert_eq!(Balances::balance_on_hold(&HoldReason::Escrow.into(), &ALICE), 10);
+		// Refunded 50 blocks later if the series is still open
+		assert_eq!(
+			Escrows::<Test>::get(EscrowPurpose::Series, ALICE),
+			Some(Escrow { amount: 10, timeout_at: 51 })
+		);
+		assert_eq!(Committed::<Test>::get(), 20);
+		assert_eq!(
+			OpenSeries::<Test>::get(ALICE),
+			Some(Series { guess: CoinSide::Head, length: 3, stake: 10, heads: 0, tails: 0 })
+		);
+
+		let expected_event = RuntimeEvent::CoinGame(Event::SeriesOpened {
+			who: ALICE,
+			guess: CoinSide::Head,
+			length: 3,
+			stake: 10,
+		});
+		assert_eq!(last_event(), expected_event);
+	});
+}
+
+/// Call: open_series
+/// Unhappy path: test that the series length is checked and only one series is open at a time
+#[test]
+fn open_series_fails_with_invalid_length_or_open_series() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			CoinGame::open_series(RuntimeOrigin::signed(ALICE), CoinSide::Head, 4, 10),
+			Error::<Test>::InvalidSeriesLength
+		);
+		assert_noop!(
+			CoinGame::open_series(RuntimeOrigin::signed(ALICE), CoinSide::Head, 7, 10),
+			Error::<Test>::InvalidSeriesLength
+		);
+
+		assert_ok!(CoinGame::open_series(RuntimeOrigin::signed(ALICE), CoinSide::Head, 3, 10));
+		assert_noop!(
+			CoinGame::open_series(RuntimeOrigin::signed(ALICE), CoinSide::Tail, 3, 10),
+			Error::<Test>::SeriesAlreadyOpen
+		);
+	});
+}
+
+/// Call: toss_series
+/// Happy path: test that the series resolves once a side reaches the majority and the winner
+/// is paid
+#[test]
+fn toss_series_resolves_on_majority() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::open_series(RuntimeOrigin::signed(ALICE), CoinSide::Head, 3, 10));
+
+		assert_ok!(CoinGame::toss_series(RuntimeOrigin::signed(ALICE)));
+		assert_eq!(SeriesTosses::<Test>::get(ALICE), Some(0));
+		settle_wagers(true);
+		assert_eq!(SeriesTosses::<Test>::get(ALICE), None);
+		assert_eq!(
+			OpenSeries::<Test>::get(ALICE),
+			Some(Series { guess: CoinSide::Head, length: 3, stake: 10, heads: 1, tails: 0 })
+		);
+
+		assert_ok!(CoinGame::toss_series(RuntimeOrigin::signed(ALICE)));
+		settle_wagers(true);
+		assert!(OpenSeries::<Test>::get(ALICE).is_none());
+
+		// Each toss names its id and the beacon round it was resolved with
+		let expected_event = RuntimeEvent::CoinGame(Event::SeriesTossed {
+			toss_id: 1,
+			who: ALICE,
+			result: CoinSide::Head,
+			heads: 2,
+			tails: 0,
+			randomness: RandomnessSource::Beacon { round: 4 },
+		});
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+		assert_eq!(Claimable::<Test>::get(ALICE), 20);
+		let expected_event = RuntimeEvent::CoinGame(Event::SeriesWon { who: ALICE, payout: 20 });
+		assert_eq!(last_event(), expected_event);
+	});
+}
+
+/// Call: toss_series
+/// Happy path: test that a lost series keeps the stake in the house
+#[test]
+fn toss_series_lost_keeps_stake() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::open_series(RuntimeOrigin::signed(ALICE), CoinSide::Head, 1, 10));
+
+		assert_ok!(CoinGame::toss_series(RuntimeOrigin::signed(ALICE)));
+		settle_wagers(false);
+
+		assert!(OpenSeries::<Test>::get(ALICE).is_none());
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE - 10);
+		let expected_event = RuntimeEvent::CoinGame(Event::SeriesLost { who: ALICE, stake: 10 });
+		assert_eq!(last_event(), expected_event);
+	});
+}
+
+/// Hook: on_initialize
+/// Happy path: test that a series still open at its expiry is closed and its stake refunded,
+/// and that its pending toss does not count for the next series
+#[test]
+fn open_series_is_refunded_after_expiry() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::open_series(RuntimeOrigin::signed(ALICE), CoinSide::Head, 3, 10));
+		assert_ok!(CoinGame::toss_series(RuntimeOrigin::signed(ALICE)));
+
+		System::set_block_number(50);
+		CoinGame::on_initialize(50);
+		assert!(OpenSeries::<Test>::contains_key(ALICE));
+
+		System::set_block_number(51);
+		CoinGame::on_initialize(51);
+		assert!(!OpenSeries::<Test>::contains_key(ALICE));
+		assert_eq!(SeriesTosses::<Test>::get(ALICE), None);
+		assert_eq!(Balances::balance_on_hold(&HoldReason::Escrow.into(), &ALICE), 0);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE);
+		assert_eq!(Committed::<Test>::get(), 0);
+		let expected_event = RuntimeEvent::CoinGame(Event::SeriesExpired { who: ALICE, stake: 10 });
+		assert_eq!(last_event(), expected_event);
+
+		// The toss of the expired series is refunded rather than counted for the new one
+		assert_ok!(CoinGame::open_series(RuntimeOrigin::signed(ALICE), CoinSide::Head, 3, 10));
+		assert_ok!(CoinGame::toss_series(RuntimeOrigin::signed(ALICE)));
+		assert_eq!(BeaconWagers::<Test>::get(3).len(), 2);
+		settle_wagers(true);
+
+		let series = OpenSeries::<Test>::get(ALICE).expect("the new series is still open");
+		assert_eq!(series.heads + series.tails, 1);
+		assert_eq!(SeriesTosses::<Test>::get(ALICE), None);
+		let expected_event =
+			RuntimeEvent::CoinGame(Event::WagerRefunded { toss_id: 0, who: ALICE, round: 3 });
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+	});
+}
+
+/// Hook: on_initialize
+/// Happy path: test that the expiry of a resolved series leaves the next series of the player
+/// open
+#[test]
+fn series_expiry_leaves_newer_series_open() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::open_series(RuntimeOrigin::signed(ALICE), CoinSide::Head, 1, 10));
+		assert_ok!(CoinGame::toss_series(RuntimeOrigin::signed(ALICE)));
+		settle_wagers(true);
+		assert!(!OpenSeries::<Test>::contains_key(ALICE));
+
+		System::set_block_number(10);
+		assert_ok!(CoinGame::open_series(RuntimeOrigin::signed(ALICE), CoinSide::Head, 3, 10));
+
+		System::set_block_number(51);
+		CoinGame::on_initialize(51);
+		assert!(OpenSeries::<Test>::contains_key(ALICE));
+		assert_eq!(Balances::balance_on_hold(&HoldReason::Escrow.into(), &ALICE), 10);
+	});
+}
+
+/// Call: toss_series
+/// Unhappy path: test that tossing without an open series fails
+#[test]
+fn toss_series_fails_with_series_not_found() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(CoinGame::toss_series(RuntimeOrigin::signed(ALICE)), Error::<Test>::SeriesNotFound);
+	});
+}
+
+/// Call: toss_series
+/// Unhappy path: test that the next toss of a series waits for the previous one to be resolved
+#[test]
+fn toss_series_fails_while_toss_is_pending() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::open_series(RuntimeOrigin::signed(ALICE), CoinSide::Head, 3, 10));
+		assert_ok!(CoinGame::toss_series(RuntimeOrigin::signed(ALICE)));
+
+		assert_noop!(
+			CoinGame::toss_series(RuntimeOrigin::signed(ALICE)),
+			Error::<Test>::SeriesTossPending
+		);
+
+		settle_wagers(true);
+		assert_ok!(CoinGame::toss_series(RuntimeOrigin::signed(ALICE)));
+	});
+}
+
+/// Call: toss_series
+/// Happy path: test that series tosses of the same block are resolved each with their own toss
+/// id, so they do not all land on the same side
+#[test]
+fn toss_series_in_same_block_lands_on_mixed_sides() {
+	new_test_ext().execute_with(|| {
+		for who in [ALICE, BOB, CHARLIE] {
+			assert_ok!(CoinGame::open_series(RuntimeOrigin::signed(who), CoinSide::Head, 5, 10));
+			assert_ok!(CoinGame::toss_series(RuntimeOrigin::signed(who)));
+		}
+
+		let wagers = BeaconWagers::<Test>::get(3);
+		let toss_ids: Vec<u64> = wagers.iter().map(|wager| wager.toss_id).collect();
+		assert_eq!(toss_ids, vec![0, 1, 2]);
+
+		let side = |randomness: &[u8; 32], toss_id| {
+			CoinSide::from_outcome(CoinGame::beacon_outcome(randomness, toss_id, 2))
+		};
+		let pulse = (1..=u8::MAX)
+			.map(|byte| beacon_pulse(3, byte))
+			.find(|pulse| {
+				toss_ids.iter().any(|id| side(&pulse.randomness, *id) == CoinSide::Head) &&
+					toss_ids.iter().any(|id| side(&pulse.randomness, *id) == CoinSide::Tail)
+			})
+			.expect("some pulse lands the tosses on both sides");
+		assert_ok!(CoinGame::submit_beacon_pulse(RuntimeOrigin::signed(BOB), pulse.clone()));
+
+		for (who, toss_id) in [ALICE, BOB, CHARLIE].into_iter().zip(toss_ids) {
+			let series = OpenSeries::<Test>::get(who).expect("the series is still open");
+			match side(&pulse.randomness, toss_id) {
+				CoinSide::Head => assert_eq!((series.heads, series.tails), (1, 0)),
+				CoinSide::Tail => assert_eq!((series.heads, series.tails), (0, 1)),
+			}
+		}
+		let heads: u32 = OpenSeries::<Test>::iter_values().map(|series| series.heads).sum();
+		assert!(heads > 0 && heads < 3);
+	});
+}
+
+/// Call: double_or_nothing
+/// Happy path: test that winnings stay in the house and can be rolled into the next toss
+#[test]
+fn double_or_nothing_keeps_winnings_pending() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+
+		assert_ok!(double_or_nothing(ALICE, Some(10), true));
+		assert_eq!(Streaks::<Test>::get(ALICE), Some(Streak { pending: 20, length: 1, expires_at: 11 }));
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE - 10);
+		assert_eq!(Balances::free_balance(CoinGame::account_id()), HOUSE_FUNDS + 10);
+		assert_eq!(Committed::<Test>::get(), 20);
+
+		System::set_block_number(2);
+		assert_ok!(double_or_nothing(ALICE, None, true));
+		assert_eq!(Streaks::<Test>::get(ALICE), Some(Streak { pending: 40, length: 2, expires_at: 12 }));
+		assert!(StreakExpiries::<Test>::get(11).is_empty());
+		assert_eq!(StreakExpiries::<Test>::get(12).into_inner(), vec![ALICE]);
+
+		let expected_event = RuntimeEvent::CoinGame(Event::StreakWon { who: ALICE, pending: 40, length: 2 });
+		assert_eq!(last_event(), expected_event);
+	});
+}
+
+/// Call: double_or_nothing
+/// Happy path: test that the streak is paid out when it reaches the maximum length
+#[test]
+fn double_or_nothing_settles_at_max_streak() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+
+		assert_ok!(double_or_nothing(ALICE, Some(10), true));
+		assert_ok!(double_or_nothing(ALICE, None, true));
+		assert_ok!(double_or_nothing(ALICE, None, true));
+
+		assert_eq!(Streaks::<Test>::get(ALICE), None);
+		assert_eq!(Claimable::<Test>::get(ALICE), 80);
+		let expected_event = RuntimeEvent::CoinGame(Event::StreakSettled { who: ALICE, amount: 80 });
+		assert_eq!(last_event(), expected_event);
+	});
+}
+
+/// Call: double_or_nothing
+/// Happy path: test that a lost toss forfeits the pending winnings
+#[test]
+fn double_or_nothing_loses_pending_winnings() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+		assert_ok!(double_or_nothing(ALICE, Some(10), true));
+		assert_ok!(double_or_nothing(ALICE, None, false));
+
+		assert_eq!(Streaks::<Test>::get(ALICE), None);
+		assert!(StreakExpiries::<Test>::get(11).is_empty());
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE - 10);
+		assert_eq!(Committed::<Test>::get(), 0);
+		assert!(System::events().iter().any(|record| matches!(
+			&record.event,
+			RuntimeEvent::CoinGame(Event::CoinNotGuessed {
+				toss_id: 1,
+				who: ALICE,
+				guess: CoinSide::Head,
+				stake: 20,
+				..
+			})
+		)));
+	});
+}
+
+/// Call: double_or_nothing
+/// Unhappy path: test that a streak is either started with a stake or rolled without one
+#[test]
+fn double_or_nothing_fails_with_wrong_stake() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+
+		assert_noop!(
+			CoinGame::double_or_nothing(RuntimeOrigin::signed(ALICE), CoinSide::Head, None),
+			Error::<Test>::NoPendingWinnings
+		);
+		assert_noop!(
+			CoinGame::double_or_nothing(RuntimeOrigin::signed(ALICE), CoinSide::Head, Some(0)),
+			Error::<Test>::InvalidStake
+		);
+
+		assert_ok!(double_or_nothing(ALICE, Some(10), true));
+		assert_noop!(
+			CoinGame::double_or_nothing(RuntimeOrigin::signed(ALICE), CoinSide::Head, Some(10)),
+			Error::<Test>::StreakAlreadyOpen
+		);
+	});
+}
+
+/// Call: double_or_nothing
+/// Unhappy path: test that a streak is neither rolled, cashed out nor started again while its
+/// toss waits for the beacon
+#[test]
+fn double_or_nothing_fails_while_toss_is_pending() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+		assert_ok!(double_or_nothing(ALICE, Some(10), true));
+
+		assert_ok!(CoinGame::double_or_nothing(RuntimeOrigin::signed(ALICE), CoinSide::Head, None));
+		assert_eq!(StreakTosses::<Test>::get(ALICE), Some(1));
+		assert_eq!(Streaks::<Test>::get(ALICE), None);
+		// The pending winnings of 20 are rolled into a payout of 40
+		assert_eq!(Committed::<Test>::get(), 40);
+
+		assert_noop!(
+			CoinGame::double_or_nothing(RuntimeOrigin::signed(ALICE), CoinSide::Head, Some(10)),
+			Error::<Test>::StreakTossPending
+		);
+		assert_noop!(CoinGame::cash_out_streak(RuntimeOrigin::signed(ALICE)), Error::<Test>::NoPendingWinnings);
+
+		settle_wagers(true);
+		assert_eq!(StreakTosses::<Test>::get(ALICE), None);
+		assert_eq!(Streaks::<Test>::get(ALICE), Some(Streak { pending: 40, length: 2, expires_at: 11 }));
+	});
+}
+
+/// Call: cash_out_streak
+/// Happy path: test that cashing out credits the pending winnings of the streak
+#[test]
+fn cash_out_streak_credits_pending_winnings() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+		assert_ok!(double_or_nothing(ALICE, Some(10), true));
+
+		assert_ok!(CoinGame::cash_out_streak(RuntimeOrigin::signed(ALICE)));
+
+		assert_eq!(Streaks::<Test>::get(ALICE), None);
+		assert!(StreakExpiries::<Test>::get(11).is_empty());
+		assert_eq!(Claimable::<Test>::get(ALICE), 20);
+		assert_noop!(CoinGame::cash_out_streak(RuntimeOrigin::signed(ALICE)), Error::<Test>::NoPendingWinnings);
+	});
+}
+
+/// Hook: on_initialize
+/// Happy path: test that expired streaks are paid out to the player
+#[test]
+fn expired_streak_is_settled() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+		assert_ok!(double_or_nothing(ALICE, Some(10), true));
+
+		System::set_block_number(11);
+		CoinGame::on_initialize(11);
+
+		assert_eq!(Streaks::<Test>::get(ALICE), None);
+		assert_eq!(Claimable::<Test>::get(ALICE), 20);
+		let expected_event = RuntimeEvent::CoinGame(Event::StreakSettled { who: ALICE, amount: 20 });
+		assert_eq!(last_event(), expected_event);
+	});
+}
+
+/// Pulse of the beacon round fixed for the global round `round` flipping it on `side`
+fn round_pulse(round: u32, side: CoinSide) -> BeaconPulse {
+	let beacon_round = RoundBeaconRounds::<Test>::get(round).expect("the round is open");
+	let toss_id = BeaconWagers::<Test>::get(beacon_round)
+		.iter()
+		.find(|wager| wager.kind == WagerKind::GlobalRound { round })
+		.expect("the round waits on the beacon")
+		.toss_id;
+	(1..=u8::MAX)
+		.map(|byte| beacon_pulse(beacon_round, byte))
+		.find(|pulse| {
+			CoinSide::from_outcome(CoinGame::beacon_outcome(&pulse.randomness, toss_id, 2)) == side
+		})
+		.expect("some pulse flips the round on the side")
+}
+
+/// Call: join_round
+/// Happy path: test that joining a round records the entry and holds the stake in escrow
+#[test]
+fn join_round_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::join_round(RuntimeOrigin::signed(ALICE), CoinSide::Head, 100));
+
+		assert_eq!(RoundEntries::<Test>::get(0).into_inner(), vec![(ALICE, CoinSide::Head, 100)]);
+		// The round ends at block 10, expected while beacon round 2 is published, and its flip
+		// waits on the beacon round published two rounds later
+		assert_eq!(RoundBeaconRounds::<Test>::get(0), Some(4));
+		assert_eq!(
+			BeaconWagers::<Test>::get(4).into_inner(),
+			vec![BeaconWager {
+				toss_id: 0,
+				who: CoinGame::account_id(),
+				kind: WagerKind::GlobalRound { round: 0 },
+			}]
+		);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE - 100);
+		assert_eq!(Balances::balance_on_hold(&HoldReason::Escrow.into(), &ALICE), 100);
+		// Refunded 20 blocks after the end of the round if it is never resolved
+		assert_eq!(
+			Escrows::<Test>::get(EscrowPurpose::Round(0), ALICE),
+			Some(Escrow { amount: 100, timeout_at: 30 })
+		);
+		assert_eq!(
+			EscrowTimeouts::<Test>::get(30).into_inner(),
+			vec![(EscrowPurpose::Round(0), ALICE)]
+		);
+
+		let expected_event = RuntimeEvent::CoinGame(Event::RoundJoined {
+			who: ALICE,
+			round: 0,
+			guess: CoinSide::Head,
+			stake: 100,
+		});
+		assert_eq!(last_event(), expected_event);
+	});
+}
+
+/// Call: join_round
+/// Unhappy path: test that a player joins a round only once and with a stake
+#[test]
+fn join_round_fails_when_already_joined() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			CoinGame::join_round(RuntimeOrigin::signed(ALICE), CoinSide::Head, 0),
+			Error::<Test>::InvalidStake
+		);
+		assert_ok!(CoinGame::join_round(RuntimeOrigin::signed(ALICE), CoinSide::Head, 100));
+		assert_noop!(
+			CoinGame::join_round(RuntimeOrigin::signed(ALICE), CoinSide::Tail, 100),
+			Error::<Test>::AlreadyInRound
+		);
+	});
+}
+
+/// Call: join_round
+/// Unhappy path: test that nobody joins a round once its beacon round is published or imported
+#[test]
+fn join_round_fails_once_beacon_round_is_published() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::join_round(RuntimeOrigin::signed(ALICE), CoinSide::Head, 100));
+
+		Now::set(BEACON_GENESIS_TIME + 3 * BEACON_PERIOD);
+		assert_noop!(
+			CoinGame::join_round(RuntimeOrigin::signed(BOB), CoinSide::Head, 100),
+			Error::<Test>::RoundClosed
+		);
+
+		Now::set(BEACON_GENESIS_TIME);
+		assert_ok!(CoinGame::join_round(RuntimeOrigin::signed(BOB), CoinSide::Head, 100));
+		assert_ok!(CoinGame::submit_beacon_pulse(
+			RuntimeOrigin::signed(BOB),
+			round_pulse(0, CoinSide::Head)
+		));
+		assert_noop!(
+			CoinGame::join_round(RuntimeOrigin::signed(CHARLIE), CoinSide::Head, 100),
+			Error::<Test>::RoundClosed
+		);
+	});
+}
+
+/// Call: join_round
+/// Happy path: test that the beacon round resolving a global round is published after its end,
+/// so that players can join until late in the window
+#[test]
+fn join_round_late_in_the_window_works() {
+	new_test_ext().execute_with(|| {
+		// Round 0 lasts two minutes, from block 0 to block 20
+		RoundLength::set(20);
+		assert_ok!(CoinGame::join_round(RuntimeOrigin::signed(ALICE), CoinSide::Head, 100));
+
+		// Block 20 is expected while beacon round 4 is published
+		assert_eq!(CoinGame::beacon_round_at(20), 4);
+		assert_eq!(RoundBeaconRounds::<Test>::get(0), Some(6));
+		assert!(CoinGame::beacon_round_block(6) > 20);
+
+		// Block 19 comes while beacon round 4 is published
+		System::set_block_number(19);
+		Now::set(BEACON_GENESIS_TIME + 3 * BEACON_PERIOD + 18);
+		assert_ok!(CoinGame::join_round(RuntimeOrigin::signed(BOB), CoinSide::Tail, 100));
+		assert_eq!(RoundEntries::<Test>::get(0).len(), 2);
+	});
+}
+
+/// Call: submit_beacon_pulse
+/// Happy path: test that winners split the losers' pool pro rata minus the fee once the beacon
+/// round of the global round is imported
+#[test]
+fn round_is_resolved_by_its_beacon_round() {
+	new_test_ext().execute_with(|| {
+		let result = CoinSide::Head;
+		assert_ok!(CoinGame::join_round(RuntimeOrigin::signed(ALICE), CoinSide::Head, 100));
+		assert_ok!(CoinGame::join_round(RuntimeOrigin::signed(BOB), CoinSide::Tail, 300));
+		assert_ok!(CoinGame::join_round(RuntimeOrigin::signed(CHARLIE), CoinSide::Head, 200));
+
+		// The end of the round no longer flips the coin
+		System::set_block_number(10);
+		CoinGame::on_initialize(10);
+		assert!(RoundResults::<Test>::get(0).is_none());
+
+		assert_ok!(CoinGame::submit_beacon_pulse(
+			RuntimeOrigin::signed(BOB),
+			round_pulse(0, CoinSide::Head)
+		));
+
+		// Losers' pool of 300 minus a 10% fee, split 1:2 between the winners
+		assert_eq!(Claimable::<Test>::get(ALICE), 100 + 90);
+		assert_eq!(Claimable::<Test>::get(BOB), 0);
+		assert_eq!(Claimable::<Test>::get(CHARLIE), 200 + 180);
+		assert_eq!(CoinGame::available_bankroll(), HOUSE_FUNDS + 30);
+		// Stakes moved from escrow to the house
+		assert_eq!(Balances::balance_on_hold(&HoldReason::Escrow.into(), &BOB), 0);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE - 300);
+		assert!(!Escrows::<Test>::contains_key(EscrowPurpose::Round(0), BOB));
+
+		let round_result =
+			RoundResult { result, winners: 2, winners_pool: 300, losers_pool: 300, fee: 30 };
+		assert_eq!(RoundResults::<Test>::get(0), Some(round_result.clone()));
+		assert!(RoundEntries::<Test>::get(0).is_empty());
+		let expected_event = RuntimeEvent::CoinGame(Event::RoundResolved {
+			toss_id: 0,
+			round: 0,
+			result: round_result,
+			randomness: RandomnessSource::Beacon { round: 4 },
+		});
+		assert_eq!(last_event(), expected_event);
+	});
+}
+
+/// Call: submit_beacon_pulse
+/// Happy path: test that the flip of a global round can be reproduced from its event
+#[test]
+fn round_resolved_event_identifies_the_flip() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::join_round(RuntimeOrigin::signed(ALICE), CoinSide::Head, 100));
+		let pulse = beacon_pulse(4, 7);
+		assert_ok!(CoinGame::submit_beacon_pulse(RuntimeOrigin::signed(BOB), pulse.clone()));
+
+		let RuntimeEvent::CoinGame(Event::RoundResolved { toss_id, result: round_result, randomness, .. }) =
+			last_event()
+		else {
+			panic!("the round is resolved")
+		};
+		assert_eq!(randomness, RandomnessSource::Beacon { round: 4 });
+
+		let random_hash = BlakeTwo256::hash_of(&(pulse.randomness, toss_id));
+		let outcome = CoinGame::reduce_random_hash(random_hash, 2);
+		assert_eq!(CoinSide::from_outcome(outcome), round_result.result);
+	});
+}
+
+/// Call: submit_beacon_pulse
+/// Happy path: test that stakes are refunded when nobody guessed the flip
+#[test]
+fn round_without_winners_refunds_stakes() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::join_round(RuntimeOrigin::signed(ALICE), CoinSide::Tail, 100));
+
+		assert_ok!(CoinGame::submit_beacon_pulse(
+			RuntimeOrigin::signed(BOB),
+			round_pulse(0, CoinSide::Head)
+		));
+
+		assert_eq!(Claimable::<Test>::get(ALICE), 100);
+		assert_eq!(RoundResults::<Test>::get(0).unwrap().fee, 0);
+	});
+}
+
+/// Call: create_tournament
+/// Happy path: test that a tournament is created open for registration and the deposit held
+#[test]
+fn create_tournament_works() {
+	new_test_ext().execute_with(|| {
+		TournamentDeposit::set(50);
+		assert_ok!(CoinGame::create_tournament(RuntimeOrigin::signed(ALICE), 100, 4, 5));
+
+		assert_eq!(
+			Tournaments::<Test>::get(0),
+			Some(Tournament {
+				organizer: ALICE,
+				entry_fee: 100,
+				max_players: 4,
+				start_block: 5,
+				deposit: 50,
+				status: TournamentStatus::Registration,
+				prize_pool: 0,
+			})
+		);
+		assert_eq!(ActiveTournaments::<Test>::get().into_inner(), vec![0]);
+		assert_eq!(Balances::balance_on_hold(&HoldReason::TournamentDeposit.into(), &ALICE), 50);
+
+		let expected_event = RuntimeEvent::CoinGame(Event::TournamentCreated { id: 0, organizer: ALICE });
+		assert_eq!(last_event(), expected_event);
+	});
+}
+
+/// Call: create_tournament
+/// Unhappy path: test that the number of players, the start block and the deposit are checked
+#[test]
+fn create_tournament_fails_with_invalid_parameters() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			CoinGame::create_tournament(RuntimeOrigin::signed(ALICE), 100, 1, 5),
+			Error::<Test>::InvalidTournament
+		);
+		assert_noop!(
+			CoinGame::create_tournament(RuntimeOrigin::signed(ALICE), 100, 5, 5),
+			Error::<Test>::InvalidTournament
+		);
+		assert_noop!(
+			CoinGame::create_tournament(RuntimeOrigin::signed(ALICE), 100, 4, 1),
+			Error::<Test>::InvalidTournament
+		);
+		// Starting after the registration period of 20 blocks
+		assert_noop!(
+			CoinGame::create_tournament(RuntimeOrigin::signed(ALICE), 100, 4, 22),
+			Error::<Test>::InvalidTournament
+		);
+		assert_ok!(CoinGame::create_tournament(RuntimeOrigin::signed(ALICE), 100, 4, 21));
+
+		TournamentDeposit::set(INITIAL_BALANCE + 1);
+		assert_noop!(
+			CoinGame::create_tournament(RuntimeOrigin::signed(BOB), 100, 4, 5),
+			TokenError::FundsUnavailable
+		);
+	});
+}
+
+/// Call: cancel_tournament
+/// Happy path: test that the organizer cancels a tournament, releasing the entry fees and the
+/// deposit
+#[test]
+fn cancel_tournament_works() {
+	new_test_ext().execute_with(|| {
+		TournamentDeposit::set(50);
+		assert_ok!(CoinGame::create_tournament(RuntimeOrigin::signed(ALICE), 100, 4, 5));
+		assert_ok!(CoinGame::register_for_tournament(RuntimeOrigin::signed(BOB), 0));
+		assert_ok!(CoinGame::register_for_tournament(RuntimeOrigin::signed(CHARLIE), 0));
+
+		assert_ok!(CoinGame::cancel_tournament(RuntimeOrigin::signed(ALICE), 0));
+
+		let tournament = Tournaments::<Test>::get(0).unwrap();
+		assert_eq!(tournament.status, TournamentStatus::Cancelled);
+		assert_eq!(tournament.deposit, 0);
+		assert!(!TournamentPlayers::<Test>::contains_key(0));
+		assert!(ActiveTournaments::<Test>::get().is_empty());
+		assert_eq!(Balances::balance_on_hold(&HoldReason::TournamentDeposit.into(), &ALICE), 0);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE);
+		assert_eq!(Balances::free_balance(CHARLIE), INITIAL_BALANCE);
+
+		let expected_event = RuntimeEvent::CoinGame(Event::TournamentCancelled { id: 0 });
+		assert_eq!(last_event(), expected_event);
+	});
+}
+
+/// Call: cancel_tournament
+/// Unhappy path: test that only the organizer cancels, and only during registration
+#[test]
+fn cancel_tournament_fails_for_others_or_after_start() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			CoinGame::cancel_tournament(RuntimeOrigin::signed(ALICE), 0),
+			Error::<Test>::TournamentNotFound
+		);
+
+		assert_ok!(CoinGame::create_tournament(RuntimeOrigin::signed(ALICE), 100, 4, 5));
+		assert_ok!(CoinGame::register_for_tournament(RuntimeOrigin::signed(BOB), 0));
+		assert_ok!(CoinGame::register_for_tournament(RuntimeOrigin::signed(CHARLIE), 0));
+		assert_noop!(
+			CoinGame::cancel_tournament(RuntimeOrigin::signed(BOB), 0),
+			Error::<Test>::NotTournamentOrganizer
+		);
+
+		System::set_block_number(5);
+		CoinGame::on_initialize(5);
+		assert_noop!(
+			CoinGame::cancel_tournament(RuntimeOrigin::signed(ALICE), 0),
+			Error::<Test>::RegistrationClosed
+		);
+	});
+}
+
+/// Call: register_for_tournament
+/// Unhappy path: test that players register once, while there is room and before the start
+#[test]
+fn register_for_tournament_fails_when_closed_full_or_registered() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::create_tournament(RuntimeOrigin::signed(ALICE), 100, 2, 5));
+
+		assert_ok!(CoinGame::register_for_tournament(RuntimeOrigin::signed(ALICE), 0));
+		assert_noop!(
+			CoinGame::register_for_tournament(RuntimeOrigin::signed(ALICE), 0),
+			Error::<Test>::AlreadyRegistered
+		);
+		assert_ok!(CoinGame::register_for_tournament(RuntimeOrigin::signed(BOB), 0));
+		assert_noop!(
+			CoinGame::register_for_tournament(RuntimeOrigin::signed(CHARLIE), 0),
+			Error::<Test>::TournamentFull
+		);
+		assert_noop!(
+			CoinGame::register_for_tournament(RuntimeOrigin::signed(CHARLIE), 1),
+			Error::<Test>::TournamentNotFound
+		);
+
+		assert_ok!(CoinGame::create_tournament(RuntimeOrigin::signed(ALICE), 100, 2, 5));
+		System::set_block_number(5);
+		assert_noop!(
+			CoinGame::register_for_tournament(RuntimeOrigin::signed(CHARLIE), 1),
+			Error::<Test>::RegistrationClosed
+		);
+	});
+}
+
+/// Hook: on_initialize
+/// Happy path: test that the bracket is played one round per block and the prize pool is paid
+/// to the top finishers
+#[test]
+fn tournament_is_played_across_blocks() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::create_tournament(RuntimeOrigin::signed(ALICE), 100, 4, 5));
+		for player in [ALICE, BOB, CHARLIE] {
+			assert_ok!(CoinGame::register_for_tournament(RuntimeOrigin::signed(player), 0));
+		}
+		assert_eq!(Tournaments::<Test>::get(0).unwrap().prize_pool, 300);
+		assert_eq!(Balances::balance_on_hold(&HoldReason::Escrow.into(), &BOB), 100);
+
+		// The bracket is generated at the start block, and the entry fees move to the house
+		System::set_block_number(5);
+		CoinGame::on_initialize(5);
+		assert_eq!(Balances::balance_on_hold(&HoldReason::Escrow.into(), &BOB), 0);
+		assert_eq!(Committed::<Test>::get(), 300);
+		assert_eq!(Balances::free_balance(CoinGame::account_id()), HOUSE_FUNDS + 300);
+		let mut bracket = TournamentPlayers::<Test>::get(0).into_inner();
+		bracket.sort();
+		assert_eq!(bracket, vec![ALICE, BOB, CHARLIE]);
+		assert_eq!(Tournaments::<Test>::get(0).unwrap().status, TournamentStatus::InProgress { round: 0 });
+
+		// Three players: one match and one bye
+		System::set_block_number(6);
+		CoinGame::on_initialize(6);
+		let first_round = TournamentMatches::<Test>::get(0, 0);
+		assert_eq!(first_round.len(), 2);
+		assert_eq!(first_round[1].second, None);
+		assert_eq!(TournamentPlayers::<Test>::get(0).len(), 2);
+		let randomness = RandomnessSource::Block {
+			block: 6,
+			subject: (CoinFlipperPalletId::get(), (b"tournament", 0u32, 0u32)).encode(),
+		};
+		let expected_event =
+			RuntimeEvent::CoinGame(Event::TournamentRoundPlayed { id: 0, round: 0, randomness });
+		assert_eq!(last_event(), expected_event);
+
+		// Final
+		System::set_block_number(7);
+		CoinGame::on_initialize(7);
+		let final_match = TournamentMatches::<Test>::get(0, 1)[0].clone();
+		let champion = final_match.winner.clone();
+		let runner_up =
+			if champion == final_match.first { final_match.second.unwrap() } else { final_match.first };
+
+		assert_eq!(Tournaments::<Test>::get(0).unwrap().status, TournamentStatus::Finished);
+		assert!(ActiveTournaments::<Test>::get().is_empty());
+		assert_eq!(Claimable::<Test>::get(champion), 210);
+		assert_eq!(Claimable::<Test>::get(runner_up), 90);
+
+		let expected_event =
+			RuntimeEvent::CoinGame(Event::TournamentFinished {
+			id: 0,
+			champion,
+			champion_prize: 210,
+			runner_up,
+			runner_up_prize: 90,
+		});
+		assert_eq!(last_event(), expected_event);
+	});
+}
+
+/// Hook: on_initialize
+/// Happy path: test that the bye of an odd bracket is drawn at random rather than always given
+/// to the last player
+#[test]
+fn tournament_bye_is_drawn_at_random() {
+	new_test_ext().execute_with(|| {
+		let mut bye_positions = Vec::new();
+		for id in 0..8 {
+			let now = System::block_number();
+			assert_ok!(CoinGame::create_tournament(RuntimeOrigin::signed(ALICE), 10, 3, now + 1));
+			for player in [ALICE, BOB, CHARLIE] {
+				assert_ok!(CoinGame::register_for_tournament(RuntimeOrigin::signed(player), id));
+			}
+
+			System::set_block_number(now + 1);
+			CoinGame::on_initialize(now + 1);
+			let bracket = TournamentPlayers::<Test>::get(id).into_inner();
+
+			System::set_block_number(now + 2);
+			CoinGame::on_initialize(now + 2);
+			let bye = TournamentMatches::<Test>::get(id, 0)
+				.into_iter()
+				.find(|played| played.second.is_none())
+				.unwrap()
+				.first;
+			bye_positions.push(bracket.iter().position(|player| *player == bye).unwrap());
+
+			System::set_block_number(now + 3);
+			CoinGame::on_initialize(now + 3);
+			assert_eq!(Tournaments::<Test>::get(id).unwrap().status, TournamentStatus::Finished);
+		}
+
+		assert!(bye_positions.iter().any(|position| *position != 2));
+	});
+}
+
+/// Hook: on_initialize
+/// Happy path: test that a tournament without enough players is cancelled and the entry fees
+/// released
+#[test]
+fn tournament_without_enough_players_is_cancelled() {
+	new_test_ext().execute_with(|| {
+		TournamentDeposit::set(50);
+		assert_ok!(CoinGame::create_tournament(RuntimeOrigin::signed(ALICE), 100, 4, 5));
+		assert_ok!(CoinGame::register_for_tournament(RuntimeOrigin::signed(BOB), 0));
+
+		System::set_block_number(5);
+		CoinGame::on_initialize(5);
+
+		assert_eq!(Tournaments::<Test>::get(0).unwrap().status, TournamentStatus::Cancelled);
+		assert_eq!(Balances::balance_on_hold(&HoldReason::Escrow.into(), &BOB), 0);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE);
+		assert_eq!(Committed::<Test>::get(), 0);
+		assert!(ActiveTournaments::<Test>::get().is_empty());
+	});
+}
+
+/// Call: buy_tickets
+/// Happy path: test that tickets are recorded for the current draw and paid into the pot
+#[test]
+fn buy_tickets_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(3);
+		assert_ok!(CoinGame::buy_tickets(RuntimeOrigin::signed(ALICE), 2));
+		assert_ok!(CoinGame::buy_tickets(RuntimeOrigin::signed(BOB), 1));
+
+		assert_eq!(LotteryTickets::<Test>::get(0).into_inner(), vec![ALICE, ALICE, BOB]);
+		assert_eq!(LotteryPot::<Test>::get(), 30);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE - 20);
+
+		let expected_event = RuntimeEvent::CoinGame(Event::TicketsBought { who: BOB, draw: 0, count: 1 });
+		assert_eq!(last_event(), expected_event);
+	});
+}
+
+/// Call: buy_tickets
+/// Unhappy path: test that the number of tickets is checked against the draw limit
+#[test]
+fn buy_tickets_fails_with_invalid_count() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			CoinGame::buy_tickets(RuntimeOrigin::signed(ALICE), 0),
+			Error::<Test>::InvalidTicketCount
+		);
+
+		assert_ok!(CoinGame::buy_tickets(RuntimeOrigin::signed(ALICE), 6));
+		assert_noop!(
+			CoinGame::buy_tickets(RuntimeOrigin::signed(BOB), 3),
+			Error::<Test>::TooManyTickets
+		);
+	});
+}
+
+/// Hook: on_initialize
+/// Happy path: test that the draw pays the pot minus the house cut to the drawn ticket
+#[test]
+fn lottery_draw_pays_winner() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CoinGame::buy_tickets(RuntimeOrigin::signed(ALICE), 3));
+		assert_ok!(CoinGame::buy_tickets(RuntimeOrigin::signed(BOB), 2));
+		let tickets = LotteryTickets::<Test>::get(0);
+
+		System::set_block_number(10);
+		CoinGame::on_initialize(10);
+
+		let random_hash = CoinGame::random_hash(&(b"lottery", 0u32));
+		let winner = tickets[CoinGame::reduce_random_hash(random_hash, 5) as usize];
+		let result = LotteryResult { winner, tickets: 5, prize: 45, house_cut: 5 };
+
+		assert_eq!(LotteryResults::<Test>::get(0), Some(result.clone()));
+		assert_eq!(LotteryPot::<Test>::get(), 0);
+		assert!(LotteryTickets::<Test>::get(0).is_empty());
+		assert_eq!(Claimable::<Test>::get(winner), 45);
+
+		let randomness = RandomnessSource::Block {
+			block: 10,
+			subject: (CoinFlipperPalletId::get(), (b"lottery", 0u32)).encode(),
+		};
+		let expected_event =
+			RuntimeEvent::CoinGame(Event::LotteryDrawn { draw: 0, result, randomness });
+		assert_eq!(last_event(), expected_event);
+	});
+}
+
+/// Hook: on_initialize
+/// Happy path: test that the weight of a draw grows with the number of tickets
+#[test]
+fn lottery_draw_weight_grows_with_tickets() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::buy_tickets(RuntimeOrigin::signed(ALICE), 1));
+		System::set_block_number(10);
+		let one_ticket = CoinGame::on_initialize(10);
+
+		assert_ok!(CoinGame::buy_tickets(RuntimeOrigin::signed(ALICE), 8));
+		System::set_block_number(20);
+		let many_tickets = CoinGame::on_initialize(20);
+
+		assert!(many_tickets.all_gt(one_ticket));
+	});
+}
+
+/// Hook: on_initialize
+/// Happy path: test that a draw without tickets rolls the pot over to the next one
+#[test]
+fn lottery_pot_rolls_over_without_tickets() {
+	new_test_ext().execute_with(|| {
+		LotteryPot::<Test>::put(50);
+
+		System::set_block_number(10);
+		CoinGame::on_initialize(10);
+
+		assert_eq!(LotteryPot::<Test>::get(), 50);
+		assert_eq!(LotteryResults::<Test>::get(0), None);
+		let expected_event = RuntimeEvent::CoinGame(Event::LotteryRolledOver { draw: 0, pot: 50 });
+		assert_eq!(last_event(), expected_event);
+
+		assert_ok!(CoinGame::buy_tickets(RuntimeOrigin::signed(ALICE), 1));
+		System::set_block_number(20);
+		CoinGame::on_initialize(20);
+
+		assert_eq!(
+			LotteryResults::<Test>::get(1),
+			Some(LotteryResult { winner: ALICE, tickets: 1, prize: 54, house_cut: 6 })
+		);
+		assert_eq!(Claimable::<Test>::get(ALICE), 54);
+	});
+}
+
+/// Call: toss_many
+/// Happy path: test that every toss of the batch is resolved and settled with a summary
+#[test]
+fn toss_many_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+
+		// Every toss of the batch has its own outcome for the same beacon round
+		let pulse = (1..=u8::MAX)
+			.map(|byte| beacon_pulse(3, byte))
+			.find(|pulse| {
+				let results: Vec<CoinSide> = (0..4)
+					.map(|toss_id| {
+						CoinGame::beacon_coin_side(&pulse.randomness, toss_id, Permill::from_percent(50))
+					})
+					.collect();
+				results == vec![CoinSide::Head, CoinSide::Tail, CoinSide::Tail, CoinSide::Head]
+			})
+			.unwrap();
+		// Guess the first two tosses right and the last two wrong
+		let guesses = vec![CoinSide::Head, CoinSide::Tail, CoinSide::Head, CoinSide::Tail];
+
+		assert_ok!(CoinGame::toss_many(
+			RuntimeOrigin::signed(ALICE),
+			guesses.try_into().unwrap(),
+			10
+		));
+		assert_eq!(NextTossId::<Test>::get(), 4);
+		assert_eq!(Committed::<Test>::get(), 80);
+
+		assert_ok!(CoinGame::submit_beacon_pulse(RuntimeOrigin::signed(BOB), pulse));
+
+		assert_eq!(CoinGame::get_coin(&ALICE), Ok(Coin::fair(CoinSide::Head)));
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE - 40);
+		assert_eq!(Claimable::<Test>::get(ALICE), 40);
+		assert_eq!(CoinGame::available_bankroll(), HOUSE_FUNDS);
+
+		let expected_event = RuntimeEvent::CoinGame(Event::TossesBatched {
+			who: ALICE,
+			first_toss_id: 0,
+			tosses: 4,
+			won: 2,
+			staked: 40,
+			paid_out: 40,
+		});
+		assert_eq!(last_event(), expected_event);
+	});
+}
+
+/// Call: toss_many
+/// Unhappy path: test that a batch needs a coin and at least one guess
+#[test]
+fn toss_many_fails_without_coin_or_guesses() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			CoinGame::toss_many(RuntimeOrigin::signed(ALICE), vec![CoinSide::Head].try_into().unwrap(), 10),
+			Error::<Test>::CoinNotFound
+		);
+
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+		assert_noop!(
+			CoinGame::toss_many(RuntimeOrigin::signed(ALICE), Default::default(), 10),
+			Error::<Test>::EmptyBatch
+		);
+	});
+}
+
+/// Call: toss_coin
+/// Unhappy path: test that an account has to wait for the cooldown between tosses
+#[test]
+fn toss_coin_fails_during_cooldown() {
+	new_test_ext().execute_with(|| {
+		TossCooldown::set(2);
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+
+		System::set_block_number(1);
+		assert_ok!(CoinGame::toss_coin(RuntimeOrigin::signed(ALICE), CoinSide::Head, 0));
+		assert_eq!(
+			TossActivities::<Test>::get(ALICE),
+			Some(TossActivity { last_toss: 1, period: 0, tosses: 1 })
+		);
+
+		System::set_block_number(2);
+		assert_noop!(
+			CoinGame::toss_coin(RuntimeOrigin::signed(ALICE), CoinSide::Head, 0),
+			Error::<Test>::TooSoon
+		);
+
+		System::set_block_number(3);
+		assert_ok!(CoinGame::toss_coin(RuntimeOrigin::signed(ALICE), CoinSide::Head, 0));
+	});
+}
+
+/// Call: toss_many
+/// Unhappy path: test that the tosses of an account are capped per period
+#[test]
+fn tosses_are_capped_per_period() {
+	new_test_ext().execute_with(|| {
+		MaxTossesPerPeriod::set(3);
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+
+		System::set_block_number(1);
+		assert_ok!(CoinGame::toss_many(
+			RuntimeOrigin::signed(ALICE),
+			vec![CoinSide::Head; 2].try_into().unwrap(),
+			0
+		));
+		assert_noop!(
+			CoinGame::toss_many(RuntimeOrigin::signed(ALICE), vec![CoinSide::Head; 2].try_into().unwrap(), 0),
+			Error::<Test>::TooSoon
+		);
+		assert_ok!(CoinGame::toss_coin(RuntimeOrigin::signed(ALICE), CoinSide::Head, 0));
+
+		// The count is reset in the next period
+		System::set_block_number(10);
+		assert_ok!(CoinGame::toss_many(
+			RuntimeOrigin::signed(ALICE),
+			vec![CoinSide::Head; 3].try_into().unwrap(),
+			0
+		));
+	});
+}
+
+/// Extension: CheckCoinGame
+/// Unhappy path: test that rate limited tosses are rejected by the transaction pool
+#[test]
+fn check_coin_game_rejects_rate_limited_tosses() {
+	new_test_ext().execute_with(|| {
+		TossCooldown::set(2);
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+
+		System::set_block_number(1);
+		let call = RuntimeCall::CoinGame(crate::Call::toss_coin { coin_side: CoinSide::Head, stake: 0 });
+		let info = Default::default();
+		assert_ok!(CheckCoinGame::<Test>::new().validate(&ALICE, &call, &info, 0));
+
+		assert_ok!(CoinGame::toss_coin(RuntimeOrigin::signed(ALICE), CoinSide::Head, 0));
+		let expected: TransactionValidity =
+			InvalidTransaction::Custom(crate::extension::TOO_SOON).into();
+		assert_eq!(CheckCoinGame::<Test>::new().validate(&ALICE, &call, &info, 0), expected);
+
+		// Other calls are not rate limited
+		let call = RuntimeCall::CoinGame(crate::Call::remove_coin {});
+		assert_ok!(CheckCoinGame::<Test>::new().validate(&ALICE, &call, &info, 0));
+	});
+}
+
+/// Extension: CheckCoinGame
+/// Unhappy path: test that calls needing a coin are rejected by the transaction pool for accounts
+/// without one, and that a second coin is rejected too
+#[test]
+fn check_coin_game_rejects_calls_without_coin() {
+	new_test_ext().execute_with(|| {
+		let info = Default::default();
+		let not_found: TransactionValidity =
+			InvalidTransaction::Custom(crate::extension::COIN_NOT_FOUND).into();
+
+		for call in [
+			crate::Call::toss_coin { coin_side: CoinSide::Head, stake: 10 },
+			crate::Call::toss_coin_with_beacon { coin_side: CoinSide::Head },
+			crate::Call::toss_many { guesses: vec![CoinSide::Head].try_into().unwrap(), stake: 10 },
+			crate::Call::double_or_nothing { coin_side: CoinSide::Head, stake: Some(10) },
+			crate::Call::remove_coin {},
+		] {
+			let call = RuntimeCall::CoinGame(call);
+			assert_eq!(CheckCoinGame::<Test>::new().validate(&ALICE, &call, &info, 0), not_found);
+		}
+
+		let call = RuntimeCall::CoinGame(crate::Call::create_coin { head_probability: None });
+		assert_ok!(CheckCoinGame::<Test>::new().validate(&ALICE, &call, &info, 0));
+
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+		let expected: TransactionValidity =
+			InvalidTransaction::Custom(crate::extension::COIN_ALREADY_EXISTS).into();
+		assert_eq!(CheckCoinGame::<Test>::new().validate(&ALICE, &call, &info, 0), expected);
+
+		// Calls that don't need a coin are let through
+		let call = RuntimeCall::CoinGame(crate::Call::buy_tickets { count: 1 });
+		assert_ok!(CheckCoinGame::<Test>::new().validate(&BOB, &call, &info, 0));
+	});
+}
+
+/// Call: toss_coin
+/// Happy path: test that coin holders get a quota of feeless tosses refreshed every period
+#[test]
+fn toss_coin_is_feeless_within_quota() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+
+		System::set_block_number(1);
+		for _ in 0..2 {
+			let post_info = CoinGame::toss_coin(RuntimeOrigin::signed(ALICE), CoinSide::Head, 0).unwrap();
+			assert_eq!(post_info.pays_fee, Pays::No);
+		}
+		let post_info = CoinGame::toss_coin(RuntimeOrigin::signed(ALICE), CoinSide::Head, 0).unwrap();
+		assert_eq!(post_info.pays_fee, Pays::Yes);
+		assert_eq!(FreeTossesUsed::<Test>::get(ALICE), (0, 2));
+
+		System::set_block_number(10);
+		let post_info = CoinGame::toss_coin(RuntimeOrigin::signed(ALICE), CoinSide::Head, 0).unwrap();
+		assert_eq!(post_info.pays_fee, Pays::No);
+		assert_eq!(FreeTossesUsed::<Test>::get(ALICE), (1, 1));
+	});
+}
+
+/// Call: toss_coin
+/// Unhappy path: test that failed tosses don't use the free quota
+#[test]
+fn failed_toss_does_not_use_free_quota() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			CoinGame::toss_coin(RuntimeOrigin::signed(ALICE), CoinSide::Head, 0),
+			Error::<Test>::CoinNotFound
+		);
+		assert_eq!(FreeTossesUsed::<Test>::get(ALICE), (0, 0));
+	});
+}
+
+/// Call: toss_coin
+/// Happy path: test that the toss event carries everything needed to verify the toss
+#[test]
+fn toss_coin_event_identifies_the_toss() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+		assert_ok!(CoinGame::toss_coin(RuntimeOrigin::signed(ALICE), CoinSide::Head, 0));
+		assert_ok!(CoinGame::toss_coin(RuntimeOrigin::signed(ALICE), CoinSide::Head, 10));
+		assert_eq!(
+			last_event(),
+			RuntimeEvent::CoinGame(Event::WagerQueued { toss_id: 1, who: ALICE, round: 3 })
+		);
+
+		let randomness = settle_wagers(true);
+
+		let expected_event = RuntimeEvent::CoinGame(Event::CoinGuessed {
+			toss_id: 1,
+			who: ALICE,
+			guess: CoinSide::Head,
+			result: CoinSide::Head,
+			stake: 10,
+			payout: 20,
+			randomness: RandomnessSource::Beacon { round: 3 },
+		});
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+
+		// The result can be reproduced from the randomness of the round and the toss id
+		let random_hash = BlakeTwo256::hash_of(&(randomness, 1u64));
+		let outcome = CoinGame::reduce_random_hash(random_hash, Permill::ACCURACY);
+		assert_eq!(CoinSide::from_biased_outcome(outcome, Permill::from_percent(50)), CoinSide::Head);
+		assert_eq!(NextTossId::<Test>::get(), 2);
+	});
+}
+
+/// Hook: on_killed_account
+/// Happy path: test that the coin of a reaped account is removed and its slot freed
+#[test]
+fn coin_is_removed_when_account_is_reaped() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+		assert_ok!(CoinGame::toss_coin(RuntimeOrigin::signed(ALICE), CoinSide::Head, 0));
+
+		assert_ok!(<Balances as Currency<_>>::transfer(
+			&ALICE,
+			&BOB,
+			Balances::free_balance(ALICE),
+			ExistenceRequirement::AllowDeath
+		));
+
+		assert!(!System::account_exists(&ALICE));
+		assert_noop!(CoinGame::get_coin(&ALICE), Error::<Test>::CoinNotFound);
+		assert_eq!(TossActivities::<Test>::get(ALICE), None);
+		assert_eq!(FreeTossesUsed::<Test>::get(ALICE), (0, 0));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == RuntimeEvent::CoinGame(Event::CoinRemoved { who: ALICE })));
+	});
+}
+
+/// Hook: on_killed_account, on_idle
+/// Happy path: test that a coin deposit keeps its owner alive until the coin expires, after
+/// which the account is reaped and its records removed
+#[test]
+fn account_with_coin_deposit_is_reaped_once_coin_expires() {
+	new_test_ext().execute_with(|| {
+		CoinDeposit::set(50);
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+		assert_ok!(CoinGame::toss_coin(RuntimeOrigin::signed(ALICE), CoinSide::Head, 0));
+
+		// The held deposit keeps the account alive
+		let _ = <Balances as Currency<_>>::transfer(
+			&ALICE,
+			&BOB,
+			Balances::free_balance(ALICE),
+			ExistenceRequirement::AllowDeath,
+		);
+		assert!(System::account_exists(&ALICE));
+		assert!(CoinGame::get_coin(&ALICE).is_ok());
+
+		System::set_block_number(21);
+		CoinGame::on_idle(21, Weight::MAX);
+		assert_eq!(Balances::balance_on_hold(&HoldReason::CoinDeposit.into(), &ALICE), 0);
+
+		assert_ok!(<Balances as Currency<_>>::transfer(
+			&ALICE,
+			&BOB,
+			Balances::free_balance(ALICE),
+			ExistenceRequirement::AllowDeath
+		));
+		assert!(!System::account_exists(&ALICE));
+		assert_noop!(CoinGame::get_coin(&ALICE), Error::<Test>::CoinNotFound);
+		assert_eq!(CoinLastActivity::<Test>::get(ALICE), None);
+		assert_eq!(TossActivities::<Test>::get(ALICE), None);
+		assert_eq!(FreeTossesUsed::<Test>::get(ALICE), (0, 0));
+	});
+}
+
+/// Hook: on_killed_account
+/// Happy path: test that reaping an account without a coin leaves the other coins alone
+#[test]
+fn reaping_account_without_coin_keeps_other_coins() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(BOB), None));
+
+		assert_ok!(<Balances as Currency<_>>::transfer(
+			&ALICE,
+			&CHARLIE,
+			Balances::free_balance(ALICE),
+			ExistenceRequirement::AllowDeath
+		));
+
+		assert!(CoinGame::get_coin(&BOB).is_ok());
+	});
+}
+
+/// Call: create_coin
+/// Happy path: test that the coin deposit is held while the coin exists
+#[test]
+fn coin_deposit_is_held_until_removal() {
+	new_test_ext().execute_with(|| {
+		CoinDeposit::set(50);
+
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+		assert_eq!(Balances::balance_on_hold(&HoldReason::CoinDeposit.into(), &ALICE), 50);
+		// Held funds are still part of the balance of the player
+		assert_eq!(Balances::total_balance(&ALICE), INITIAL_BALANCE);
+		assert_eq!(CoinLastActivity::<Test>::get(ALICE), Some(1));
+
+		assert_ok!(CoinGame::remove_coin(RuntimeOrigin::signed(ALICE)));
+		assert_eq!(Balances::balance_on_hold(&HoldReason::CoinDeposit.into(), &ALICE), 0);
+		assert_eq!(CoinLastActivity::<Test>::get(ALICE), None);
+	});
+}
+
+/// Call: create_coin
+/// Unhappy path: test that no coin can be created once every slot is taken
+#[test]
+fn create_coin_fails_when_slots_are_taken() {
+	new_test_ext().execute_with(|| {
+		for who in 10..20 {
+			assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(who), None));
+		}
+		assert_noop!(
+			CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None),
+			Error::<Test>::TooManyCoins
+		);
+	});
+}
+
+/// Hook: on_idle
+/// Happy path: test that idle coins are removed and their deposit returned
+#[test]
+fn on_idle_reaps_expired_coins() {
+	new_test_ext().execute_with(|| {
+		CoinDeposit::set(50);
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(BOB), None));
+
+		System::set_block_number(15);
+		assert_ok!(CoinGame::toss_coin(RuntimeOrigin::signed(BOB), CoinSide::Head, 0));
+
+		System::set_block_number(21);
+		CoinGame::on_idle(21, Weight::MAX);
+
+		assert_noop!(CoinGame::get_coin(&ALICE), Error::<Test>::CoinNotFound);
+		assert_eq!(Balances::balance_on_hold(&HoldReason::CoinDeposit.into(), &ALICE), 0);
+		assert!(CoinGame::get_coin(&BOB).is_ok());
+		assert_eq!(Balances::balance_on_hold(&HoldReason::CoinDeposit.into(), &BOB), 50);
+
+		let expected_event = RuntimeEvent::CoinGame(Event::CoinExpired {
+			who: ALICE,
+			last_activity: 1,
+			deposit: 50,
+		});
+		assert_eq!(last_event(), expected_event);
+	});
+}
+
+/// Hook: on_idle
+/// Unhappy path: test that coins are only reaped within the remaining weight
+#[test]
+fn on_idle_respects_remaining_weight() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+
+		System::set_block_number(21);
+		assert_eq!(CoinGame::on_idle(21, Weight::zero()), Weight::zero());
+		assert!(CoinGame::get_coin(&ALICE).is_ok());
+
+		// Not enough weight left for a single coin
+		let weight = CoinGame::on_idle(21, RocksDbWeight::get().reads_writes(1, 1));
+		assert_eq!(weight, RocksDbWeight::get().reads_writes(1, 1));
+		assert!(CoinGame::get_coin(&ALICE).is_ok());
+
+		CoinGame::on_idle(21, Weight::MAX);
+		assert_noop!(CoinGame::get_coin(&ALICE), Error::<Test>::CoinNotFound);
+	});
+}
+
+/// Call: toss_coin
+/// Unhappy path: test that a stake above the allowed share of the bankroll is rejected
+#[test]
+fn toss_coin_fails_with_stake_above_max_bet() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+		let _ = Balances::make_free_balance_be(&CoinGame::account_id(), 5_000);
+
+		assert_noop!(
+			CoinGame::toss_coin(RuntimeOrigin::signed(ALICE), CoinSide::Head, 501),
+			Error::<Test>::StakeTooHigh
+		);
+		assert_ok!(CoinGame::toss_coin(RuntimeOrigin::signed(ALICE), CoinSide::Head, 500));
+	});
+}
+
+/// Call: roll
+/// Unhappy path: test that the solvency buffer and committed funds are not put at risk
+#[test]
+fn wagers_cannot_use_buffer_or_committed_funds() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::buy_tickets(RuntimeOrigin::signed(BOB), 5));
+		assert_eq!(Committed::<Test>::get(), 50);
+		assert_eq!(CoinGame::available_bankroll(), HOUSE_FUNDS);
+
+		SolvencyBuffer::set(HOUSE_FUNDS - 40);
+		assert_eq!(CoinGame::available_bankroll(), 40);
+
+		// Paying 60 for a stake of 10 needs 50 from the bankroll
+		assert_noop!(
+			CoinGame::roll(RuntimeOrigin::signed(ALICE), 6, 1, 10),
+			Error::<Test>::InsufficientHouseFunds
+		);
+	});
+}
+
+/// Function: pay_out
+/// Happy path: test that the circuit breaker pauses wagering until the admin resumes it
+#[test]
+fn circuit_breaker_pauses_wagering() {
+	new_test_ext().execute_with(|| {
+		CircuitBreakerThreshold::set(HOUSE_FUNDS);
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+
+		assert_ok!(win_toss(ALICE, 10));
+		assert!(WageringPaused::<Test>::get());
+		let expected_event =
+			RuntimeEvent::CoinGame(Event::WageringPaused { available: HOUSE_FUNDS - 10 });
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+
+		assert_noop!(
+			CoinGame::toss_coin(RuntimeOrigin::signed(ALICE), CoinSide::Head, 10),
+			Error::<Test>::WageringPaused
+		);
+		// Free games don't put the bankroll at risk
+		assert_ok!(CoinGame::toss_coin(RuntimeOrigin::signed(ALICE), CoinSide::Head, 0));
+
+		assert_noop!(
+			CoinGame::set_wagering_paused(RuntimeOrigin::signed(ALICE), false),
+			DispatchError::BadOrigin
+		);
+		CircuitBreakerThreshold::set(0);
+		assert_ok!(CoinGame::set_wagering_paused(RuntimeOrigin::root(), false));
+		assert_eq!(last_event(), RuntimeEvent::CoinGame(Event::WageringResumed));
+		assert_ok!(CoinGame::toss_coin(RuntimeOrigin::signed(ALICE), CoinSide::Head, 10));
+	});
+}
+
+/// Call: fund_house, withdraw_from_house
+/// Happy path: test that the house can be funded by anyone and withdrawn from by the admin
+#[test]
+fn house_can_be_funded_and_withdrawn() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::fund_house(RuntimeOrigin::signed(ALICE), 100));
+		assert_eq!(Balances::free_balance(CoinGame::account_id()), HOUSE_FUNDS + 100);
+		assert_eq!(
+			last_event(),
+			RuntimeEvent::CoinGame(Event::HouseFunded { who: ALICE, amount: 100 })
+		);
+
+		assert_noop!(
+			CoinGame::withdraw_from_house(RuntimeOrigin::signed(ALICE), ALICE, 100),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(CoinGame::withdraw_from_house(RuntimeOrigin::root(), BOB, 500));
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE + 500);
+		assert_eq!(
+			last_event(),
+			RuntimeEvent::CoinGame(Event::HouseWithdrawn { dest: BOB, amount: 500 })
+		);
+	});
+}
+
+/// Call: withdraw_from_house
+/// Unhappy path: test that funds committed to players cannot be withdrawn
+#[test]
+fn withdraw_from_house_fails_above_available_bankroll() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::buy_tickets(RuntimeOrigin::signed(ALICE), 2));
+		assert_eq!(Committed::<Test>::get(), 20);
+
+		assert_noop!(
+			CoinGame::withdraw_from_house(RuntimeOrigin::root(), BOB, HOUSE_FUNDS + 1),
+			Error::<Test>::InsufficientHouseFunds
+		);
+		assert_ok!(CoinGame::withdraw_from_house(RuntimeOrigin::root(), BOB, HOUSE_FUNDS - 1));
+	});
+}
+
+/// Call: provide_liquidity
+/// Happy path: test that liquidity mints shares at the current value of the house
+#[test]
+fn provide_liquidity_mints_shares() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::provide_liquidity(RuntimeOrigin::signed(ALICE), 100));
+
+		// Funds put in before any share was minted belong to the house
+		assert_eq!(LiquidityShares::<Test>::get(CoinGame::account_id()), HOUSE_FUNDS);
+		assert_eq!(LiquidityShares::<Test>::get(ALICE), 100);
+		assert_eq!(TotalShares::<Test>::get(), HOUSE_FUNDS + 100);
+		assert_eq!(CoinGame::share_value(100), 100);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE - 100);
+		assert_eq!(
+			last_event(),
+			RuntimeEvent::CoinGame(Event::LiquidityProvided { who: ALICE, amount: 100, shares: 100 })
+		);
+
+		// The house doubled its value
+		let _ = Balances::make_free_balance_be(&CoinGame::account_id(), 2 * (HOUSE_FUNDS + 100));
+		assert_ok!(CoinGame::provide_liquidity(RuntimeOrigin::signed(BOB), 100));
+		assert_eq!(LiquidityShares::<Test>::get(BOB), 50);
+		assert_eq!(CoinGame::share_value(100), 200);
+	});
+}
+
+/// Call: provide_liquidity
+/// Unhappy path: test that an amount worth less than one share is rejected
+#[test]
+fn provide_liquidity_fails_below_one_share() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::provide_liquidity(RuntimeOrigin::signed(ALICE), 100));
+		let _ = Balances::make_free_balance_be(&CoinGame::account_id(), 2 * (HOUSE_FUNDS + 100));
+
+		assert_noop!(
+			CoinGame::provide_liquidity(RuntimeOrigin::signed(BOB), 1),
+			Error::<Test>::TooLittleLiquidity
+		);
+		assert_noop!(
+			CoinGame::provide_liquidity(RuntimeOrigin::signed(BOB), 0),
+			Error::<Test>::TooLittleLiquidity
+		);
+	});
+}
+
+/// Call: provide_liquidity, withdraw_liquidity, redeem_liquidity
+/// Happy path: test that requested shares are locked and redeemed for their value at the request
+#[test]
+fn redeem_liquidity_pays_value_at_request() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::provide_liquidity(RuntimeOrigin::signed(ALICE), 500));
+		// The house doubled its value while the shares were held
+		let _ = Balances::make_free_balance_be(&CoinGame::account_id(), 2 * (HOUSE_FUNDS + 500));
+
+		assert_ok!(CoinGame::withdraw_liquidity(RuntimeOrigin::signed(ALICE), 500));
+		assert!(!LiquidityShares::<Test>::contains_key(ALICE));
+		assert_eq!(TotalShares::<Test>::get(), HOUSE_FUNDS + 500);
+		assert_eq!(
+			LiquidityWithdrawals::<Test>::get(ALICE),
+			Some(LiquidityWithdrawal { shares: 500, value: 1_000, unlock_at: 6, expires_at: 16 })
+		);
+		assert_eq!(
+			last_event(),
+			RuntimeEvent::CoinGame(Event::LiquidityWithdrawalRequested {
+				who: ALICE,
+				shares: 500,
+				value: 1_000,
+				unlock_at: 6,
+				expires_at: 16
+			})
+		);
+
+		// Gains made after the request stay with the other holders
+		let _ = Balances::make_free_balance_be(&CoinGame::account_id(), 4 * (HOUSE_FUNDS + 500));
+
+		System::set_block_number(6);
+		assert_ok!(CoinGame::redeem_liquidity(RuntimeOrigin::signed(ALICE)));
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE - 500 + 1_000);
+		assert_eq!(LiquidityWithdrawals::<Test>::get(ALICE), None);
+		assert_eq!(TotalShares::<Test>::get(), HOUSE_FUNDS);
+		assert_eq!(CoinGame::share_value(HOUSE_FUNDS), 4 * (HOUSE_FUNDS + 500) - 1_000);
+		assert_eq!(
+			last_event(),
+			RuntimeEvent::CoinGame(Event::LiquidityRedeemed { who: ALICE, shares: 500, amount: 1_000 })
+		);
+	});
+}
+
+/// Call: withdraw_liquidity, redeem_liquidity
+/// Happy path: test that shares waiting for redemption keep taking the losses of the house
+#[test]
+fn redeem_liquidity_takes_losses_since_request() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::provide_liquidity(RuntimeOrigin::signed(ALICE), 500));
+		assert_ok!(CoinGame::withdraw_liquidity(RuntimeOrigin::signed(ALICE), 500));
+
+		// The house lost half its value
+		let _ = Balances::make_free_balance_be(&CoinGame::account_id(), (HOUSE_FUNDS + 500) / 2);
+
+		System::set_block_number(6);
+		assert_ok!(CoinGame::redeem_liquidity(RuntimeOrigin::signed(ALICE)));
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE - 500 + 250);
+	});
+}
+
+/// Call: withdraw_liquidity, redeem_liquidity
+/// Unhappy path: test that a standing request neither exits at will with the gains of the house
+/// nor stays redeemable forever
+#[test]
+fn standing_withdrawal_request_is_no_free_exit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::provide_liquidity(RuntimeOrigin::signed(ALICE), 500));
+		assert_ok!(CoinGame::withdraw_liquidity(RuntimeOrigin::signed(ALICE), 500));
+
+		// The locked shares cannot be requested a second time
+		assert_noop!(
+			CoinGame::withdraw_liquidity(RuntimeOrigin::signed(ALICE), 501),
+			Error::<Test>::InsufficientShares
+		);
+
+		// The house doubles its value, the request still pays its value at the request
+		let _ = Balances::make_free_balance_be(&CoinGame::account_id(), 2 * (HOUSE_FUNDS + 500));
+		System::set_block_number(15);
+		assert_ok!(CoinGame::redeem_liquidity(RuntimeOrigin::signed(ALICE)));
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE);
+
+		// An expired request gives the shares back instead of paying them
+		assert_ok!(CoinGame::provide_liquidity(RuntimeOrigin::signed(ALICE), 500));
+		let shares = LiquidityShares::<Test>::get(ALICE);
+		assert_ok!(CoinGame::withdraw_liquidity(RuntimeOrigin::signed(ALICE), shares));
+		System::set_block_number(30);
+		assert_ok!(CoinGame::redeem_liquidity(RuntimeOrigin::signed(ALICE)));
+		assert_eq!(LiquidityShares::<Test>::get(ALICE), shares);
+		assert_eq!(LiquidityWithdrawals::<Test>::get(ALICE), None);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE - 500);
+		assert_eq!(
+			last_event(),
+			RuntimeEvent::CoinGame(Event::LiquidityWithdrawalExpired { who: ALICE, shares })
+		);
+		assert_noop!(
+			CoinGame::redeem_liquidity(RuntimeOrigin::signed(ALICE)),
+			Error::<Test>::WithdrawalNotFound
+		);
+	});
+}
+
+/// Call: provide_liquidity
+/// Unhappy path: test that the first provider of a worthless house cannot inflate the price of
+/// a share by donating to the house to take the deposits of the next providers
+#[test]
+fn provide_liquidity_resists_donation_inflation() {
+	new_test_ext().execute_with(|| {
+		let _ = Balances::make_free_balance_be(&CoinGame::account_id(), 0);
+		let _ = Balances::make_free_balance_be(&ALICE, 10_000);
+
+		assert_noop!(
+			CoinGame::provide_liquidity(RuntimeOrigin::signed(ALICE), 1_000),
+			Error::<Test>::TooLittleLiquidity
+		);
+		assert_ok!(CoinGame::provide_liquidity(RuntimeOrigin::signed(ALICE), 1_001));
+		assert_eq!(LiquidityShares::<Test>::get(ALICE), 1);
+		assert_eq!(LiquidityShares::<Test>::get(CoinGame::account_id()), 1_000);
+
+		// The attacker donates to the house to inflate the price of their single share
+		assert_ok!(<Balances as Currency<_>>::transfer(
+			&ALICE,
+			&CoinGame::account_id(),
+			5_000,
+			ExistenceRequirement::KeepAlive
+		));
+
+		assert_ok!(CoinGame::provide_liquidity(RuntimeOrigin::signed(BOB), 100));
+		assert_eq!(LiquidityShares::<Test>::get(BOB), 16);
+		// The next provider loses less than a share to rounding, the attacker most of the
+		// donation to the locked shares
+		assert_eq!(CoinGame::share_value(16), 95);
+		assert_eq!(CoinGame::share_value(1), 5);
+	});
+}
+
+/// Call: withdraw_liquidity, redeem_liquidity
+/// Unhappy path: test that shares are redeemed only once requested and unlocked
+#[test]
+fn redeem_liquidity_fails_before_delay() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::provide_liquidity(RuntimeOrigin::signed(ALICE), 500));
+
+		assert_noop!(
+			CoinGame::withdraw_liquidity(RuntimeOrigin::signed(ALICE), 501),
+			Error::<Test>::InsufficientShares
+		);
+		assert_noop!(
+			CoinGame::redeem_liquidity(RuntimeOrigin::signed(ALICE)),
+			Error::<Test>::WithdrawalNotFound
+		);
+
+		assert_ok!(CoinGame::withdraw_liquidity(RuntimeOrigin::signed(ALICE), 200));
+		System::set_block_number(5);
+		assert_noop!(
+			CoinGame::redeem_liquidity(RuntimeOrigin::signed(ALICE)),
+			Error::<Test>::WithdrawalLocked
+		);
+
+		// A new request restarts the delay
+		assert_ok!(CoinGame::withdraw_liquidity(RuntimeOrigin::signed(ALICE), 300));
+		System::set_block_number(6);
+		assert_noop!(
+			CoinGame::redeem_liquidity(RuntimeOrigin::signed(ALICE)),
+			Error::<Test>::WithdrawalLocked
+		);
+		System::set_block_number(10);
+		assert_ok!(CoinGame::redeem_liquidity(RuntimeOrigin::signed(ALICE)));
+		assert_eq!(LiquidityShares::<Test>::get(ALICE), 200);
+	});
+}
+
+/// Function: mint_shares, share_value
+/// Happy path: test that providing and redeeming at any price never takes value from the holders
+#[test]
+fn liquidity_round_trip_never_profits() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::provide_liquidity(RuntimeOrigin::signed(ALICE), 100));
+		// An awkward price of a bit more than three per share
+		let _ = Balances::make_free_balance_be(&CoinGame::account_id(), 3 * (HOUSE_FUNDS + 100) + 7);
+		let alice_value = CoinGame::share_value(100);
+
+		for amount in 1..=50 {
+			let shares_before = LiquidityShares::<Test>::get(BOB);
+			if CoinGame::provide_liquidity(RuntimeOrigin::signed(BOB), amount).is_err() {
+				continue
+			}
+			let shares = LiquidityShares::<Test>::get(BOB) - shares_before;
+			assert!(CoinGame::share_value(shares) <= amount);
+			assert!(CoinGame::share_value(100) >= alice_value);
+		}
+		assert!(CoinGame::share_value(LiquidityShares::<Test>::get(BOB)) <= (1..=50u64).sum::<u64>());
+	});
+}
+
+/// Call: provide_liquidity
+/// Unhappy path: test that nothing is provided while the shares of a drained house are worth
+/// nothing
+#[test]
+fn provide_liquidity_fails_when_shares_are_worthless() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::provide_liquidity(RuntimeOrigin::signed(ALICE), 100));
+		let _ = Balances::make_free_balance_be(&CoinGame::account_id(), 500);
+		Committed::<Test>::put(500);
+		assert_eq!(CoinGame::liquidity_value(), 0);
+
+		assert_noop!(
+			CoinGame::provide_liquidity(RuntimeOrigin::signed(BOB), 100),
+			Error::<Test>::TooLittleLiquidity
+		);
+		assert_noop!(
+			CoinGame::fund_house(RuntimeOrigin::signed(BOB), 100),
+			Error::<Test>::TooLittleLiquidity
+		);
+	});
+}
+
+/// Call: withdraw_from_house
+/// Unhappy path: test that the admin cannot withdraw the value owned by liquidity providers
+#[test]
+fn withdraw_from_house_fails_above_house_shares() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::provide_liquidity(RuntimeOrigin::signed(ALICE), 500));
+
+		assert_noop!(
+			CoinGame::withdraw_from_house(RuntimeOrigin::root(), BOB, HOUSE_FUNDS + 1),
+			Error::<Test>::InsufficientShares
+		);
+
+		assert_ok!(CoinGame::withdraw_from_house(RuntimeOrigin::root(), BOB, 1_000));
+		assert_eq!(LiquidityShares::<Test>::get(CoinGame::account_id()), HOUSE_FUNDS - 1_000);
+		assert_eq!(CoinGame::share_value(500), 500);
+	});
+}
+
+/// Call: toss_coin
+/// Happy path: test that the rake is charged on top of the stake and sent to its destination,
+/// so that a fair coin no longer costs the house the rake
+#[test]
+fn toss_coin_sends_rake_to_destination() {
+	new_test_ext().execute_with(|| {
+		Rake::set(Permill::from_percent(10));
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+
+		assert_ok!(win_toss(ALICE, 100));
+
+		assert_eq!(Balances::free_balance(TREASURY), 10);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE - 110);
+		assert_eq!(Claimable::<Test>::get(ALICE), 200);
+		assert_eq!(CoinGame::available_bankroll(), HOUSE_FUNDS + 100 - 200);
+		let expected_event = RuntimeEvent::CoinGame(Event::RakeCollected { who: ALICE, amount: 10 });
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+
+		assert_ok!(lose_toss(ALICE, 100));
+
+		// One won and one lost toss break even for the house, the player paid both rakes
+		assert_eq!(CoinGame::liquidity_value(), HOUSE_FUNDS);
+		assert_eq!(Balances::free_balance(TREASURY), 20);
+		assert_eq!(Balances::free_balance(ALICE) + Claimable::<Test>::get(ALICE), INITIAL_BALANCE - 20);
+	});
+}
+
+/// Call: join_round
+/// Happy path: test that pool games, which have their own fees, pay no rake
+#[test]
+fn join_round_pays_no_rake() {
+	new_test_ext().execute_with(|| {
+		Rake::set(Permill::from_percent(10));
+
+		assert_ok!(CoinGame::join_round(RuntimeOrigin::signed(ALICE), CoinSide::Head, 100));
+
+		assert_eq!(Balances::free_balance(TREASURY), 0);
+		assert_eq!(Balances::balance_on_hold(&HoldReason::Escrow.into(), &ALICE), 100);
+	});
+}
+
+/// Hook: on_initialize
+/// Happy path: test that an escrow still unresolved at its timeout is refunded
+#[test]
+fn escrow_is_refunded_after_timeout() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::join_round(RuntimeOrigin::signed(ALICE), CoinSide::Head, 100));
+		// The round is never resolved
+		RoundEntries::<Test>::remove(0);
+
+		System::set_block_number(29);
+		CoinGame::on_initialize(29);
+		assert_eq!(Balances::balance_on_hold(&HoldReason::Escrow.into(), &ALICE), 100);
+
+		System::set_block_number(30);
+		CoinGame::on_initialize(30);
+		assert_eq!(Balances::balance_on_hold(&HoldReason::Escrow.into(), &ALICE), 0);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE);
+		assert!(!Escrows::<Test>::contains_key(EscrowPurpose::Round(0), ALICE));
+		assert!(EscrowTimeouts::<Test>::get(30).is_empty());
+
+		let expected_event = RuntimeEvent::CoinGame(Event::EscrowReleased {
+			purpose: EscrowPurpose::Round(0),
+			who: ALICE,
+			amount: 100,
+			timed_out: true,
+		});
+		assert_eq!(last_event(), expected_event);
+	});
+}
+
+/// Hook: on_initialize
+/// Happy path: test that the wagers waiting on a beacon round that is never imported are
+/// refunded after their timeout, releasing their payouts and pending tosses
+#[test]
+fn wagers_are_refunded_when_beacon_round_is_never_imported() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(BOB), None));
+		assert_ok!(CoinGame::toss_coin(RuntimeOrigin::signed(ALICE), CoinSide::Head, 10));
+		assert_ok!(CoinGame::roll(RuntimeOrigin::signed(ALICE), 6, 1, 10));
+		assert_ok!(CoinGame::double_or_nothing(RuntimeOrigin::signed(BOB), CoinSide::Head, Some(10)));
+		assert_ok!(CoinGame::open_series(RuntimeOrigin::signed(CHARLIE), CoinSide::Head, 3, 10));
+		assert_ok!(CoinGame::toss_series(RuntimeOrigin::signed(CHARLIE)));
+
+		// Round 3 is published 60 seconds, or ten blocks, after genesis
+		assert_eq!(CoinGame::beacon_round_block(3), 11);
+		assert_eq!(Balances::balance_on_hold(&HoldReason::Escrow.into(), &ALICE), 20);
+		assert_eq!(
+			Escrows::<Test>::get(EscrowPurpose::Wager { round: 3, toss_id: 0 }, ALICE),
+			Some(Escrow { amount: 10, timeout_at: 31 })
+		);
+		assert_eq!(EscrowTimeouts::<Test>::get(31).len(), 4);
+		assert_eq!(Committed::<Test>::get(), 20 + 60 + 20 + 20);
+
+		System::set_block_number(30);
+		CoinGame::on_initialize(30);
+		assert_eq!(BeaconWagers::<Test>::get(3).len(), 4);
+
+		System::set_block_number(31);
+		CoinGame::on_initialize(31);
+		assert!(!BeaconWagers::<Test>::contains_key(3));
+		assert_eq!(Balances::balance_on_hold(&HoldReason::Escrow.into(), &ALICE), 0);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE);
+		assert_eq!(StreakTosses::<Test>::get(BOB), None);
+		assert_eq!(SeriesTosses::<Test>::get(CHARLIE), None);
+		// The series itself stays open and can be tossed again
+		assert!(OpenSeries::<Test>::contains_key(CHARLIE));
+		assert_eq!(Committed::<Test>::get(), 20);
+		for (toss_id, who) in [(0, ALICE), (1, ALICE), (2, BOB), (3, CHARLIE)] {
+			let expected_event =
+				RuntimeEvent::CoinGame(Event::WagerRefunded { toss_id, who, round: 3 });
+			assert!(System::events().iter().any(|record| record.event == expected_event));
+		}
+
+		assert_noop!(
+			CoinGame::submit_beacon_pulse(RuntimeOrigin::signed(BOB), beacon_pulse(3, 7)),
+			Error::<Test>::BeaconRoundNotAwaited
+		);
+	});
+}
+
+/// Hook: on_initialize
+/// Unhappy path: test that a resolved escrow is not refunded at its timeout
+#[test]
+fn resolved_escrow_is_not_refunded() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::join_round(RuntimeOrigin::signed(ALICE), CoinSide::Head, 100));
+
+		assert_ok!(CoinGame::submit_beacon_pulse(
+			RuntimeOrigin::signed(BOB),
+			round_pulse(0, CoinSide::Tail)
+		));
+		let free_balance = Balances::free_balance(ALICE);
+
+		System::set_block_number(30);
+		CoinGame::on_initialize(30);
+		assert_eq!(Balances::free_balance(ALICE), free_balance);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE - 100);
+	});
+}
+
+fn accept_asset() {
+	assert_ok!(CoinGame::set_asset_bet_limits(
+		RuntimeOrigin::root(),
+		ASSET,
+		Some(BetLimits { min_bet: 10, max_bet: 500, native_rate: FixedU128::from_u32(1) })
+	));
+}
+
+/// Call: toss_coin_with_asset
+/// Happy path: test that a stake in an asset is paid out from the bankroll of the asset
+#[test]
+fn toss_coin_with_asset_pays_in_asset() {
+	new_test_ext().execute_with(|| {
+		accept_asset();
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+
+		assert_ok!(CoinGame::toss_coin_with_asset(
+			RuntimeOrigin::signed(ALICE),
+			ASSET,
+			CoinSide::Head,
+			100
+		));
+		// The payout is owed by the bankroll of the asset until the toss is resolved
+		assert_eq!(AssetCommitted::<Test>::get(ASSET), 200);
+		assert_eq!(CoinGame::asset_bankroll(ASSET), HOUSE_FUNDS + 100 - 200);
+		assert_noop!(
+			CoinGame::withdraw_asset_from_house(RuntimeOrigin::root(), ASSET, BOB, HOUSE_FUNDS),
+			Error::<Test>::InsufficientHouseFunds
+		);
+
+		settle_wagers(true);
+
+		assert_eq!(AssetCommitted::<Test>::get(ASSET), 0);
+		assert_eq!(Assets::balance(ASSET, ALICE), INITIAL_BALANCE - 100 + 200);
+		assert_eq!(Assets::balance(ASSET, CoinGame::account_id()), HOUSE_FUNDS + 100 - 200);
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE);
+		assert!(System::events().iter().any(|record| matches!(
+			&record.event,
+			RuntimeEvent::CoinGame(Event::AssetCoinTossed {
+				toss_id: 0,
+				who: ALICE,
+				asset: ASSET,
+				stake: 100,
+				payout: 200,
+				..
+			})
+		)));
+	});
+}
+
+/// Call: toss_coin_with_asset
+/// Unhappy path: test that wagers are refused in assets without limits or outside the limits
+#[test]
+fn toss_coin_with_asset_fails_outside_limits() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+		assert_noop!(
+			CoinGame::toss_coin_with_asset(RuntimeOrigin::signed(ALICE), ASSET, CoinSide::Head, 100),
+			Error::<Test>::AssetNotAccepted
+		);
+
+		accept_asset();
+		assert_noop!(
+			CoinGame::toss_coin_with_asset(RuntimeOrigin::signed(ALICE), ASSET, CoinSide::Head, 9),
+			Error::<Test>::StakeOutOfLimits
+		);
+		assert_noop!(
+			CoinGame::toss_coin_with_asset(RuntimeOrigin::signed(ALICE), ASSET, CoinSide::Head, 501),
+			Error::<Test>::StakeOutOfLimits
+		);
+	});
+}
+
+/// Call: toss_coin_with_asset
+/// Unhappy path: test that the bankroll of the asset has to cover the payout
+#[test]
+fn toss_coin_with_asset_fails_with_insufficient_asset_bankroll() {
+	new_test_ext().execute_with(|| {
+		accept_asset();
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+		assert_ok!(CoinGame::withdraw_asset_from_house(
+			RuntimeOrigin::root(),
+			ASSET,
+			BOB,
+			HOUSE_FUNDS - 5
+		));
+		assert_eq!(CoinGame::asset_bankroll(ASSET), 5);
+
+		assert_noop!(
+			CoinGame::toss_coin_with_asset(RuntimeOrigin::signed(ALICE), ASSET, CoinSide::Head, 10),
+			Error::<Test>::InsufficientHouseFunds
+		);
+
+		assert_ok!(CoinGame::fund_asset_house(RuntimeOrigin::signed(BOB), ASSET, 95));
+		assert_eq!(
+			last_event(),
+			RuntimeEvent::CoinGame(Event::AssetHouseFunded { who: BOB, asset: ASSET, amount: 95 })
+		);
+		assert_ok!(CoinGame::toss_coin_with_asset(
+			RuntimeOrigin::signed(ALICE),
+			ASSET,
+			CoinSide::Head,
+			10
+		));
+	});
+}
+
+/// Call: toss_coin_with_asset
+/// Unhappy path: test that stakes in an asset are capped to a fraction of its bankroll, net of
+/// the solvency buffer, like native stakes
+#[test]
+fn toss_coin_with_asset_fails_above_bet_fraction_of_asset_bankroll() {
+	new_test_ext().execute_with(|| {
+		accept_asset();
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+		assert_ok!(CoinGame::withdraw_asset_from_house(
+			RuntimeOrigin::root(),
+			ASSET,
+			BOB,
+			HOUSE_FUNDS - 1_000
+		));
+
+		// 10% of a bankroll of 1_000
+		assert_noop!(
+			CoinGame::toss_coin_with_asset(RuntimeOrigin::signed(ALICE), ASSET, CoinSide::Head, 101),
+			Error::<Test>::StakeTooHigh
+		);
+
+		// 10% of the 500 left above the buffer
+		SolvencyBuffer::set(500);
+		assert_noop!(
+			CoinGame::toss_coin_with_asset(RuntimeOrigin::signed(ALICE), ASSET, CoinSide::Head, 51),
+			Error::<Test>::StakeTooHigh
+		);
+		assert_ok!(CoinGame::toss_coin_with_asset(
+			RuntimeOrigin::signed(ALICE),
+			ASSET,
+			CoinSide::Head,
+			50
+		));
+	});
+}
+
+/// Call: set_asset_bet_limits
+/// Unhappy path: test that only the admin sets consistent bet limits
+#[test]
+fn set_asset_bet_limits_fails_for_invalid_limits() {
+	new_test_ext().execute_with(|| {
+		let limits = BetLimits { min_bet: 10, max_bet: 500, native_rate: FixedU128::from_u32(1) };
+		assert_noop!(
+			CoinGame::set_asset_bet_limits(RuntimeOrigin::signed(ALICE), ASSET, Some(limits)),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			CoinGame::set_asset_bet_limits(
+				RuntimeOrigin::root(),
+				ASSET,
+				Some(BetLimits { min_bet: 501, max_bet: 500, native_rate: FixedU128::from_u32(1) })
+			),
+			Error::<Test>::InvalidBetLimits
+		);
+
+		accept_asset();
+		assert_ok!(CoinGame::set_asset_bet_limits(RuntimeOrigin::root(), ASSET, None));
+		assert_eq!(AssetBetLimits::<Test>::get(ASSET), None);
+		assert_eq!(
+			last_event(),
+			RuntimeEvent::CoinGame(Event::AssetBetLimitsSet { asset: ASSET, limits: None })
+		);
+	});
+}
+
+/// Call: toss_coin
+/// Happy path: test that a share of every losing stake feeds the jackpot
+#[test]
+fn losing_toss_feeds_jackpot() {
+	new_test_ext().execute_with(|| {
+		JackpotContribution::set(Permill::from_percent(10));
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+
+		assert_ok!(lose_toss(ALICE, 100));
+
+		assert_eq!(JackpotPot::<Test>::get(), 10);
+		assert_eq!(Committed::<Test>::get(), 10);
+		assert_eq!(CoinGame::available_bankroll(), HOUSE_FUNDS + 90);
+	});
+}
+
+/// Call: toss_coin
+/// Happy path: test that guessing `JackpotStreak` staked tosses in a row wins the jackpot
+#[test]
+fn toss_streak_wins_jackpot() {
+	new_test_ext().execute_with(|| {
+		JackpotContribution::set(Permill::from_percent(10));
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(BOB), None));
+		assert_ok!(lose_toss(BOB, 100));
+
+		for streak in 1..=2 {
+			assert_ok!(win_toss(ALICE, 10));
+			assert_eq!(JackpotStreaks::<Test>::get(ALICE), streak);
+		}
+		assert_ok!(win_toss(ALICE, 10));
+
+		assert_eq!(JackpotPot::<Test>::get(), 0);
+		assert_eq!(JackpotStreaks::<Test>::get(ALICE), 0);
+		assert_eq!(JackpotWinCount::<Test>::get(), 1);
+		assert_eq!(
+			JackpotWins::<Test>::get(0),
+			Some(JackpotWin { winner: ALICE, amount: 10, block: 1 })
+		);
+		assert_eq!(Claimable::<Test>::get(ALICE), 3 * 20 + 10);
+		assert_eq!(Committed::<Test>::get(), 3 * 20 + 10);
+		let expected_event =
+			RuntimeEvent::CoinGame(Event::JackpotWon { who: ALICE, index: 0, amount: 10 });
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+	});
+}
+
+/// Call: toss_coin
+/// Unhappy path: test that a lost toss, or a free one, does not count towards the jackpot
+#[test]
+fn lost_toss_resets_jackpot_streak() {
+	new_test_ext().execute_with(|| {
+		JackpotContribution::set(Permill::from_percent(10));
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+
+		for _ in 0..2 {
+			assert_ok!(win_toss(ALICE, 10));
+		}
+		assert_ok!(lose_toss(ALICE, 100));
+		assert_eq!(JackpotStreaks::<Test>::get(ALICE), 0);
+
+		assert_ok!(win_toss(ALICE, 0));
+		for _ in 0..2 {
+			assert_ok!(win_toss(ALICE, 10));
+		}
+		assert_eq!(JackpotStreaks::<Test>::get(ALICE), 2);
+		assert_eq!(JackpotPot::<Test>::get(), 10);
+		assert_eq!(JackpotWinCount::<Test>::get(), 0);
+	});
+}
+
+/// Call: toss_coin
+/// Unhappy path: test that tosses staking less than `MinJackpotStake` do not win the jackpot
+#[test]
+fn dust_tosses_do_not_win_jackpot() {
+	new_test_ext().execute_with(|| {
+		JackpotContribution::set(Permill::from_percent(10));
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(BOB), None));
+		assert_ok!(lose_toss(BOB, 100));
+
+		for _ in 0..3 {
+			assert_ok!(win_toss(ALICE, 9));
+		}
+
+		assert_eq!(JackpotStreaks::<Test>::get(ALICE), 0);
+		assert_eq!(JackpotPot::<Test>::get(), 10);
+		assert_eq!(JackpotWinCount::<Test>::get(), 0);
+	});
+}
+
+/// Call: roll, toss_series
+/// Happy path: test that lost dice rolls and series feed the jackpot
+#[test]
+fn lost_rolls_and_series_feed_jackpot() {
+	new_test_ext().execute_with(|| {
+		JackpotContribution::set(Permill::from_percent(10));
+
+		assert_ok!(CoinGame::roll(RuntimeOrigin::signed(ALICE), 6, 1, 100));
+		settle_wagers(false);
+		assert_eq!(JackpotPot::<Test>::get(), 10);
+
+		assert_ok!(CoinGame::open_series(RuntimeOrigin::signed(BOB), CoinSide::Head, 1, 100));
+		assert_ok!(CoinGame::toss_series(RuntimeOrigin::signed(BOB)));
+		settle_wagers(false);
+		assert_eq!(JackpotPot::<Test>::get(), 20);
+		assert_eq!(Committed::<Test>::get(), 20);
+	});
+}
+
+/// Call: self_exclude
+/// Happy path: test that a self-excluded player cannot wager until the exclusion ends
+#[test]
+fn self_exclude_blocks_wagers() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+		assert_ok!(CoinGame::self_exclude(RuntimeOrigin::signed(ALICE), 5));
+		assert_eq!(last_event(), RuntimeEvent::CoinGame(Event::SelfExcluded { who: ALICE, until: 6 }));
+
+		assert_noop!(lose_toss(ALICE, 10), Error::<Test>::SelfExcluded);
+		assert_noop!(
+			CoinGame::join_round(RuntimeOrigin::signed(ALICE), CoinSide::Head, 10),
+			Error::<Test>::SelfExcluded
+		);
+
+		// The exclusion cannot be shortened
+		assert_ok!(CoinGame::self_exclude(RuntimeOrigin::signed(ALICE), 1));
+		assert_eq!(SelfExclusions::<Test>::get(ALICE), Some(6));
+
+		System::set_block_number(6);
+		assert_ok!(lose_toss(ALICE, 10));
+		assert_eq!(SelfExclusions::<Test>::get(ALICE), None);
+	});
+}
+
+/// Call: set_play_limits
+/// Unhappy path: test that a stake above the stake cap is rejected
+#[test]
+fn stake_cap_limits_wagers() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+		let limits = PlayLimits { stake_cap: Some(50), ..Default::default() };
+		assert_ok!(CoinGame::set_play_limits(RuntimeOrigin::signed(ALICE), limits.clone()));
+		assert_eq!(
+			last_event(),
+			RuntimeEvent::CoinGame(Event::PlayLimitsSet { who: ALICE, limits })
+		);
+
+		assert_noop!(lose_toss(ALICE, 51), Error::<Test>::StakeCapExceeded);
+		assert_ok!(lose_toss(ALICE, 50));
+	});
+}
+
+/// Call: set_play_limits
+/// Unhappy path: test that stakes are rejected once they could exceed the loss caps
+#[test]
+fn loss_caps_limit_wagers() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+		let limits = PlayLimits {
+			daily_loss_cap: Some(100),
+			weekly_loss_cap: Some(150),
+			..Default::default()
+		};
+		assert_ok!(CoinGame::set_play_limits(RuntimeOrigin::signed(ALICE), limits));
+
+		// Winnings make up for losses
+		assert_ok!(win_toss(ALICE, 10));
+		assert_ok!(lose_toss(ALICE, 60));
+		assert_ok!(lose_toss(ALICE, 40));
+		assert_eq!(CoinGame::losses(&ALICE).daily, 100);
+		assert_noop!(lose_toss(ALICE, 1), Error::<Test>::DailyLossCapReached);
+
+		System::set_block_number(10);
+		assert_noop!(lose_toss(ALICE, 51), Error::<Test>::WeeklyLossCapReached);
+		assert_ok!(lose_toss(ALICE, 50));
+		assert_eq!(CoinGame::losses(&ALICE).daily, 50);
+		assert_eq!(CoinGame::losses(&ALICE).weekly, 150);
+	});
+}
+
+/// Call: set_play_limits
+/// Happy path: test that stricter limits apply immediately and looser ones after the delay
+#[test]
+fn set_play_limits_delays_loosening() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+		let strict = PlayLimits { stake_cap: Some(50), ..Default::default() };
+		assert_ok!(CoinGame::set_play_limits(RuntimeOrigin::signed(ALICE), strict.clone()));
+
+		assert_ok!(CoinGame::set_play_limits(RuntimeOrigin::signed(ALICE), PlayLimits::default()));
+		assert_eq!(PlayerLimits::<Test>::get(ALICE), strict);
+		assert_eq!(
+			PendingPlayerLimits::<Test>::get(ALICE),
+			Some(PendingPlayLimits { limits: PlayLimits::default(), effective_at: 6 })
+		);
+		assert_eq!(
+			last_event(),
+			RuntimeEvent::CoinGame(Event::PlayLimitsScheduled {
+				who: ALICE,
+				limits: PlayLimits::default(),
+				effective_at: 6
+			})
+		);
+		assert_noop!(lose_toss(ALICE, 60), Error::<Test>::StakeCapExceeded);
+
+		System::set_block_number(6);
+		// Reading the limits in effect stores nothing
+		assert_eq!(CoinGame::play_limits(&ALICE), PlayLimits::default());
+		assert_eq!(PlayerLimits::<Test>::get(ALICE), strict);
+		assert!(PendingPlayerLimits::<Test>::get(ALICE).is_some());
+		assert_ok!(lose_toss(ALICE, 60));
+		assert_eq!(PlayerLimits::<Test>::get(ALICE), PlayLimits::default());
+		assert_eq!(PendingPlayerLimits::<Test>::get(ALICE), None);
+
+		// Tightening cancels a pending loosening
+		assert_ok!(CoinGame::set_play_limits(RuntimeOrigin::signed(ALICE), strict.clone()));
+		assert_ok!(CoinGame::set_play_limits(RuntimeOrigin::signed(ALICE), PlayLimits::default()));
+		assert!(PendingPlayerLimits::<Test>::get(ALICE).is_some());
+		assert_ok!(CoinGame::set_play_limits(RuntimeOrigin::signed(ALICE), strict.clone()));
+		assert_eq!(PlayerLimits::<Test>::get(ALICE), strict);
+		assert_eq!(PendingPlayerLimits::<Test>::get(ALICE), None);
+	});
+}
+
+/// Call: double_or_nothing
+/// Unhappy path: test that rolling pending winnings is subject to the self-exclusion and the
+/// stake cap
+#[test]
+fn double_or_nothing_roll_respects_play_limits() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+		assert_ok!(double_or_nothing(ALICE, Some(10), true));
+
+		let limits = PlayLimits { stake_cap: Some(15), ..Default::default() };
+		assert_ok!(CoinGame::set_play_limits(RuntimeOrigin::signed(ALICE), limits));
+		assert_noop!(
+			CoinGame::double_or_nothing(RuntimeOrigin::signed(ALICE), CoinSide::Head, None),
+			Error::<Test>::StakeCapExceeded
+		);
+
+		assert_ok!(CoinGame::self_exclude(RuntimeOrigin::signed(ALICE), 5));
+		assert_noop!(
+			CoinGame::double_or_nothing(RuntimeOrigin::signed(ALICE), CoinSide::Head, None),
+			Error::<Test>::SelfExcluded
+		);
+	});
+}
+
+/// Call: toss_coin_with_asset
+/// Unhappy path: test that stakes in an asset count for their native value against the play
+/// limits
+#[test]
+fn toss_coin_with_asset_respects_play_limits() {
+	new_test_ext().execute_with(|| {
+		let limits = BetLimits { min_bet: 10, max_bet: 500, native_rate: FixedU128::from_u32(2) };
+		assert_ok!(CoinGame::set_asset_bet_limits(RuntimeOrigin::root(), ASSET, Some(limits)));
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+		let limits =
+			PlayLimits { stake_cap: Some(50), daily_loss_cap: Some(60), ..Default::default() };
+		assert_ok!(CoinGame::set_play_limits(RuntimeOrigin::signed(ALICE), limits));
+
+		assert_noop!(
+			CoinGame::toss_coin_with_asset(RuntimeOrigin::signed(ALICE), ASSET, CoinSide::Head, 30),
+			Error::<Test>::StakeCapExceeded
+		);
+		assert_ok!(CoinGame::toss_coin_with_asset(RuntimeOrigin::signed(ALICE), ASSET, CoinSide::Head, 25));
+		settle_wagers(false);
+		assert_eq!(CoinGame::losses(&ALICE).daily, 50);
+
+		assert_noop!(
+			CoinGame::toss_coin_with_asset(RuntimeOrigin::signed(ALICE), ASSET, CoinSide::Head, 10),
+			Error::<Test>::DailyLossCapReached
+		);
+
+		assert_ok!(CoinGame::self_exclude(RuntimeOrigin::signed(ALICE), 5));
+		assert_noop!(
+			CoinGame::toss_coin_with_asset(RuntimeOrigin::signed(ALICE), ASSET, CoinSide::Head, 10),
+			Error::<Test>::SelfExcluded
+		);
+	});
+}
+
+/// Hook: on_killed_account
+/// Happy path: test that the losses of a reaped account are kept for its loss caps
+#[test]
+fn losses_are_kept_when_account_is_reaped() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+		assert_ok!(lose_toss(ALICE, 10));
+
+		assert_ok!(<Balances as Currency<_>>::transfer(
+			&ALICE,
+			&BOB,
+			Balances::free_balance(ALICE),
+			ExistenceRequirement::AllowDeath
+		));
+
+		assert!(!System::account_exists(&ALICE));
+		assert_eq!(CoinGame::losses(&ALICE).daily, 10);
+	});
+}
+
+/// Call: claim
+/// Happy path: test that credited winnings are withdrawn in part or in full
+#[test]
+fn claim_withdraws_winnings() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+		assert_ok!(win_toss(ALICE, 10));
+		let expected_event = RuntimeEvent::CoinGame(Event::WinningsCredited { who: ALICE, amount: 20 });
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+		assert_eq!(Committed::<Test>::get(), 20);
+
+		assert_ok!(CoinGame::claim(RuntimeOrigin::signed(ALICE), Some(5)));
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE - 10 + 5);
+		assert_eq!(Claimable::<Test>::get(ALICE), 15);
+		assert_eq!(
+			last_event(),
+			RuntimeEvent::CoinGame(Event::WinningsClaimed { who: ALICE, amount: 5, remaining: 15 })
+		);
+
+		assert_ok!(CoinGame::claim(RuntimeOrigin::signed(ALICE), None));
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 10);
+		assert!(!Claimable::<Test>::contains_key(ALICE));
+		assert_eq!(Committed::<Test>::get(), 0);
+		assert_eq!(Balances::free_balance(CoinGame::account_id()), HOUSE_FUNDS - 10);
+	});
+}
+
+/// Call: claim
+/// Happy path: test that winnings of payouts above the threshold are claimed last and vest
+#[test]
+fn claim_vests_large_winnings() {
+	new_test_ext().execute_with(|| {
+		let rule = PayoutVesting { threshold: 20, duration: 10 };
+		assert_ok!(CoinGame::set_payout_vesting(RuntimeOrigin::root(), Some(rule.clone())));
+		assert_eq!(last_event(), RuntimeEvent::CoinGame(Event::PayoutVestingSet { rule: Some(rule) }));
+
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+		assert_ok!(win_toss(ALICE, 10));
+		assert_ok!(win_toss(ALICE, 5));
+		assert_eq!(Claimable::<Test>::get(ALICE), 30);
+		assert_eq!(VestingClaimable::<Test>::get(ALICE), 20);
+
+		// The payout of 10 is claimed first, then 5 of the payout of 20 vest
+		assert_ok!(CoinGame::claim(RuntimeOrigin::signed(ALICE), Some(15)));
+		assert_eq!(VestingClaimable::<Test>::get(ALICE), 15);
+		assert_eq!(<Vesting as VestingSchedule<u64>>::vesting_balance(&ALICE), Some(5));
+		let expected_event =
+			RuntimeEvent::CoinGame(Event::WinningsVested { who: ALICE, amount: 5, per_block: 1 });
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+
+		assert_ok!(CoinGame::claim(RuntimeOrigin::signed(ALICE), None));
+		assert!(!VestingClaimable::<Test>::contains_key(ALICE));
+		assert_eq!(<Vesting as VestingSchedule<u64>>::vesting_balance(&ALICE), Some(20));
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 15);
+		let expected_event =
+			RuntimeEvent::CoinGame(Event::WinningsVested { who: ALICE, amount: 15, per_block: 2 });
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+	});
+}
+
+/// Call: claim
+/// Unhappy path: test that only the winnings that do not vest are paid once the account has as
+/// many vesting schedules as it can hold
+#[test]
+fn claim_keeps_vesting_winnings_at_schedule_cap() {
+	new_test_ext().execute_with(|| {
+		let rule = PayoutVesting { threshold: 20, duration: 10 };
+		assert_ok!(CoinGame::set_payout_vesting(RuntimeOrigin::root(), Some(rule)));
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+		assert_ok!(win_toss(ALICE, 10));
+		assert_ok!(win_toss(ALICE, 5));
+
+		let max_schedules = <Test as pallet_vesting::Config>::MAX_VESTING_SCHEDULES;
+		for _ in 0..max_schedules {
+			assert_ok!(<Vesting as VestingSchedule<u64>>::add_vesting_schedule(&ALICE, 10, 1, 1));
+		}
+
+		assert_ok!(CoinGame::claim(RuntimeOrigin::signed(ALICE), None));
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE - 15 + 10);
+		assert_eq!(Claimable::<Test>::get(ALICE), 20);
+		assert_eq!(VestingClaimable::<Test>::get(ALICE), 20);
+		assert_eq!(
+			last_event(),
+			RuntimeEvent::CoinGame(Event::WinningsClaimed { who: ALICE, amount: 10, remaining: 20 })
+		);
+
+		assert_noop!(
+			CoinGame::claim(RuntimeOrigin::signed(ALICE), None),
+			Error::<Test>::TooManyVestingSchedules
+		);
+	});
+}
+
+/// Call: set_payout_vesting
+/// Unhappy path: test that only the admin sets a vesting rule, with a threshold and a duration
+#[test]
+fn set_payout_vesting_fails_with_invalid_rule() {
+	new_test_ext().execute_with(|| {
+		let rule = PayoutVesting { threshold: 20, duration: 10 };
+		assert_noop!(
+			CoinGame::set_payout_vesting(RuntimeOrigin::signed(ALICE), Some(rule)),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			CoinGame::set_payout_vesting(
+				RuntimeOrigin::root(),
+				Some(PayoutVesting { threshold: 20, duration: 0 })
+			),
+			Error::<Test>::InvalidPayoutVesting
+		);
+		assert_noop!(
+			CoinGame::set_payout_vesting(
+				RuntimeOrigin::root(),
+				Some(PayoutVesting { threshold: 0, duration: 10 })
+			),
+			Error::<Test>::InvalidPayoutVesting
+		);
+		assert_eq!(PayoutVestingRule::<Test>::get(), None);
+	});
+}
+
+/// Call: claim
+/// Unhappy path: test that no more than the claimable balance is withdrawn
+#[test]
+fn claim_fails_above_claimable() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			CoinGame::claim(RuntimeOrigin::signed(ALICE), None),
+			Error::<Test>::NothingToClaim
+		);
+
+		assert_ok!(CoinGame::create_coin(RuntimeOrigin::signed(ALICE), None));
+		assert_ok!(win_toss(ALICE, 10));
+		assert_noop!(
+			CoinGame::claim(RuntimeOrigin::signed(ALICE), Some(21)),
+			Error::<Test>::InsufficientClaimable
+		);
+		assert_noop!(
+			CoinGame::claim(RuntimeOrigin::signed(ALICE), Some(0)),
+			Error::<Test>::NothingToClaim
+		);
+	});
+}
+
+/// Migration: MigrateToV1
+/// Happy path: test that stored coins become fair coins showing the same side, once
+#[test]
+fn migrate_to_v1_makes_stored_coins_fair() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<CoinGame>();
+		let mut old_coins = OldCoins::<Test>::new();
+		old_coins.try_insert(ALICE, OldCoin { side: CoinSide::Head }).unwrap();
+		old_coins.try_insert(BOB, OldCoin { side: CoinSide::Tail }).unwrap();
+		unhashed::put(&CoinStorage::<Test>::hashed_key(), &old_coins);
+
+		let weight = MigrateToV1::<Test>::on_runtime_upgrade();
+		assert_eq!(weight, RocksDbWeight::get().reads_writes(2, 2));
+		assert_eq!(CoinGame::on_chain_storage_version(), 1);
+		assert_eq!(CoinGame::get_coin(&ALICE), Ok(Coin::fair(CoinSide::Head)));
+		assert_eq!(CoinGame::get_coin(&BOB), Ok(Coin::fair(CoinSide::Tail)));
+
+		// Already migrated
+		let weight = MigrateToV1::<Test>::on_runtime_upgrade();
+		assert_eq!(weight, RocksDbWeight::get().reads(1));
+		assert_eq!(CoinGame::get_coin(&ALICE), Ok(Coin::fair(CoinSide::Head)));
+	});
+}
@@ -1,27 +1,24 @@
-
-//! Autogenerated weights for pallet_template
+//! Weights for pallet_coin_game
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
-//! DATE: 2023-04-06, STEPS: `50`, REPEAT: `20`, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! WORST CASE MAP SIZE: `1000000`
-//! HOSTNAME: `Alexs-MacBook-Pro-2.local`, CPU: `<UNKNOWN>`
-//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
+//! Estimated by hand from the storage accessed by every call in its worst case, with the
+//! execution time rounded up. None of them was measured yet. The import of a beacon pulse is
+//! dominated by the BLS verification, which hashes the message to G2 and checks two pairings in
+//! Wasm. Regenerate them on the reference hardware from the benchmarks in `benchmarking.rs` with:
 
-// Executed Command:
 // ../../target/release/node-template
 // benchmark
 // pallet
 // --chain
 // dev
 // --pallet
-// pallet_template
+// pallet_coin_game
 // --extrinsic
 // *
 // --steps=50
 // --repeat=20
 // --wasm-execution=compiled
 // --output
-// pallets/template/src/weights.rs
+// pallets/coin-game/src/weights.rs
 // --template
 // ../../.maintain/frame-weight-template.hbs
 
@@ -32,22 +29,855 @@
 use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
 use core::marker::PhantomData;
 
-/// Weight functions needed for pallet_template.
+/// Weight functions needed for pallet_coin_game.
 pub trait WeightInfo {
-	fn default_weight() -> Weight;
+	fn create_coin() -> Weight;
+	fn toss_coin() -> Weight;
+	fn toss_many(b: u32, ) -> Weight;
+	fn remove_coin() -> Weight;
+	fn toss_coin_with_beacon() -> Weight;
+	fn submit_beacon_pulse(t: u32, ) -> Weight;
+	fn roll() -> Weight;
+	fn open_series() -> Weight;
+	fn toss_series() -> Weight;
+	fn double_or_nothing(s: u32, ) -> Weight;
+	fn join_round(e: u32, ) -> Weight;
+	fn create_tournament() -> Weight;
+	fn register_for_tournament(p: u32, ) -> Weight;
+	fn cancel_tournament(p: u32, ) -> Weight;
+	fn buy_tickets(c: u32, ) -> Weight;
+	fn fund_house() -> Weight;
+	fn withdraw_from_house() -> Weight;
+	fn set_wagering_paused() -> Weight;
+	fn provide_liquidity() -> Weight;
+	fn withdraw_liquidity() -> Weight;
+	fn redeem_liquidity() -> Weight;
+	fn toss_coin_with_asset() -> Weight;
+	fn set_asset_bet_limits() -> Weight;
+	fn fund_asset_house() -> Weight;
+	fn withdraw_asset_from_house() -> Weight;
+	fn set_play_limits() -> Weight;
+	fn self_exclude() -> Weight;
+	fn claim() -> Weight;
+	fn set_payout_vesting() -> Weight;
+	fn cash_out_streak(s: u32, ) -> Weight;
 }
 
-/// Weights for pallet_template using the Substrate node and recommended hardware.
+/// Weights for pallet_coin_game using the Substrate node and recommended hardware.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
-	fn default_weight() -> Weight {
-		Weight::default()
+	/// Storage: CoinGame CoinStorage (r:1 w:1)
+	/// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: CoinGame CoinLastActivity (r:0 w:1)
+	fn create_coin() -> Weight {
+		Weight::from_parts(45_000_000, 7_000)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: CoinGame CoinStorage (r:1 w:0)
+	/// Storage: CoinGame TossActivities (r:1 w:1)
+	/// Storage: CoinGame CoinLastActivity (r:0 w:1)
+	/// Storage: CoinGame WageringPaused (r:1 w:0)
+	/// Storage: CoinGame Committed (r:1 w:1)
+	/// Storage: CoinGame SelfExclusions (r:1 w:1)
+	/// Storage: CoinGame PendingPlayerLimits (r:1 w:1)
+	/// Storage: CoinGame PlayerLimits (r:1 w:1)
+	/// Storage: CoinGame Losses (r:1 w:1)
+	/// Storage: System Account (r:3 w:3)
+	/// Storage: CoinGame FreeTossesUsed (r:1 w:1)
+	/// Storage: CoinGame NextTossId (r:1 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: CoinGame LatestBeaconRound (r:1 w:0)
+	/// Storage: CoinGame BeaconWagers (r:1 w:1)
+	/// Storage: CoinGame EscrowTimeouts (r:1 w:1)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Storage: CoinGame Escrows (r:0 w:1)
+	fn toss_coin() -> Weight {
+		Weight::from_parts(85_000_000, 20_000)
+			.saturating_add(T::DbWeight::get().reads(18_u64))
+			.saturating_add(T::DbWeight::get().writes(16_u64))
+	}
+	/// Storage: CoinGame CoinStorage (r:1 w:0)
+	/// Storage: CoinGame TossActivities (r:1 w:1)
+	/// Storage: CoinGame CoinLastActivity (r:0 w:1)
+	/// Storage: CoinGame WageringPaused (r:1 w:0)
+	/// Storage: CoinGame Committed (r:1 w:1)
+	/// Storage: CoinGame SelfExclusions (r:1 w:1)
+	/// Storage: CoinGame PendingPlayerLimits (r:1 w:1)
+	/// Storage: CoinGame PlayerLimits (r:1 w:1)
+	/// Storage: CoinGame Losses (r:1 w:1)
+	/// Storage: System Account (r:3 w:3)
+	/// Storage: CoinGame NextTossId (r:1 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: CoinGame LatestBeaconRound (r:1 w:0)
+	/// Storage: CoinGame BeaconWagers (r:1 w:1)
+	/// Storage: CoinGame EscrowTimeouts (r:1 w:1)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Storage: CoinGame Escrows (r:0 w:1)
+	/// The range of component `b` is `[1, 50]`.
+	fn toss_many(b: u32, ) -> Weight {
+		Weight::from_parts(85_000_000, 20_000)
+			.saturating_add(Weight::from_parts(4_000_000, 0).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(17_u64))
+			.saturating_add(T::DbWeight::get().writes(15_u64))
+	}
+	/// Storage: CoinGame CoinStorage (r:1 w:1)
+	/// Storage: CoinGame CoinLastActivity (r:0 w:1)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn remove_coin() -> Weight {
+		Weight::from_parts(40_000_000, 7_000)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: CoinGame CoinStorage (r:1 w:0)
+	/// Storage: CoinGame TossActivities (r:1 w:1)
+	/// Storage: CoinGame CoinLastActivity (r:0 w:1)
+	/// Storage: CoinGame NextTossId (r:1 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: CoinGame LatestBeaconRound (r:1 w:0)
+	/// Storage: CoinGame BeaconWagers (r:1 w:1)
+	/// Storage: CoinGame EscrowTimeouts (r:1 w:1)
+	fn toss_coin_with_beacon() -> Weight {
+		Weight::from_parts(45_000_000, 16_000)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: CoinGame BeaconWagers (r:1 w:1)
+	/// Storage: CoinGame LatestBeaconRound (r:1 w:1)
+	/// Storage: CoinGame Committed (r:1 w:1)
+	/// Storage: CoinGame Claimable (r:1 w:1)
+	/// Storage: CoinGame VestingClaimable (r:1 w:1)
+	/// Storage: CoinGame PayoutVestingRule (r:1 w:0)
+	/// Storage: CoinGame WageringPaused (r:1 w:1)
+	/// Storage: CoinGame Losses (r:1 w:1)
+	/// Storage: CoinGame JackpotStreaks (r:1 w:1)
+	/// Storage: CoinGame JackpotPot (r:1 w:1)
+	/// Storage: CoinGame CoinStorage (r:1 w:1)
+	/// Storage: CoinGame Escrows (r:1 w:1)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: CoinGame RoundEntries (r:1 w:1)
+	/// Storage: CoinGame RoundResults (r:0 w:1)
+	/// The range of component `t` is `[1, 512]`.
+	fn submit_beacon_pulse(t: u32, ) -> Weight {
+		Weight::from_parts(45_000_000_000, 12_695)
+			.saturating_add(Weight::from_parts(60_000_000, 2_603).saturating_mul(t.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(T::DbWeight::get().reads((11_u64).saturating_mul(t.into())))
+			.saturating_add(T::DbWeight::get().writes((9_u64).saturating_mul(t.into())))
+	}
+	/// Storage: CoinGame TossActivities (r:1 w:1)
+	/// Storage: CoinGame CoinLastActivity (r:0 w:1)
+	/// Storage: CoinGame WageringPaused (r:1 w:0)
+	/// Storage: CoinGame Committed (r:1 w:1)
+	/// Storage: CoinGame SelfExclusions (r:1 w:1)
+	/// Storage: CoinGame PendingPlayerLimits (r:1 w:1)
+	/// Storage: CoinGame PlayerLimits (r:1 w:1)
+	/// Storage: CoinGame Losses (r:1 w:1)
+	/// Storage: System Account (r:3 w:3)
+	/// Storage: CoinGame NextTossId (r:1 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: CoinGame LatestBeaconRound (r:1 w:0)
+	/// Storage: CoinGame BeaconWagers (r:1 w:1)
+	/// Storage: CoinGame EscrowTimeouts (r:1 w:1)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Storage: CoinGame Escrows (r:0 w:1)
+	fn roll() -> Weight {
+		Weight::from_parts(80_000_000, 20_000)
+			.saturating_add(T::DbWeight::get().reads(16_u64))
+			.saturating_add(T::DbWeight::get().writes(15_u64))
+	}
+	/// Storage: CoinGame OpenSeries (r:1 w:1)
+	/// Storage: CoinGame WageringPaused (r:1 w:0)
+	/// Storage: CoinGame Committed (r:1 w:1)
+	/// Storage: CoinGame SelfExclusions (r:1 w:1)
+	/// Storage: CoinGame PendingPlayerLimits (r:1 w:1)
+	/// Storage: CoinGame PlayerLimits (r:1 w:1)
+	/// Storage: CoinGame Losses (r:1 w:1)
+	/// Storage: System Account (r:3 w:3)
+	/// Storage: CoinGame EscrowTimeouts (r:1 w:1)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Storage: CoinGame Escrows (r:0 w:1)
+	fn open_series() -> Weight {
+		Weight::from_parts(65_000_000, 14_000)
+			.saturating_add(T::DbWeight::get().reads(12_u64))
+			.saturating_add(T::DbWeight::get().writes(12_u64))
+	}
+	/// Storage: CoinGame OpenSeries (r:1 w:0)
+	/// Storage: CoinGame SeriesTosses (r:1 w:1)
+	/// Storage: CoinGame TossActivities (r:1 w:1)
+	/// Storage: CoinGame CoinLastActivity (r:0 w:1)
+	/// Storage: CoinGame NextTossId (r:1 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: CoinGame LatestBeaconRound (r:1 w:0)
+	/// Storage: CoinGame BeaconWagers (r:1 w:1)
+	/// Storage: CoinGame EscrowTimeouts (r:1 w:1)
+	fn toss_series() -> Weight {
+		Weight::from_parts(45_000_000, 16_000)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	/// Storage: CoinGame CoinStorage (r:1 w:0)
+	/// Storage: CoinGame StreakTosses (r:1 w:1)
+	/// Storage: CoinGame CoinLastActivity (r:0 w:1)
+	/// Storage: CoinGame Streaks (r:1 w:1)
+	/// Storage: CoinGame StreakExpiries (r:1 w:1)
+	/// Storage: CoinGame WageringPaused (r:1 w:0)
+	/// Storage: CoinGame Committed (r:1 w:1)
+	/// Storage: CoinGame SelfExclusions (r:1 w:1)
+	/// Storage: CoinGame PendingPlayerLimits (r:1 w:1)
+	/// Storage: CoinGame PlayerLimits (r:1 w:1)
+	/// Storage: CoinGame Losses (r:1 w:1)
+	/// Storage: System Account (r:3 w:3)
+	/// Storage: CoinGame NextTossId (r:1 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: CoinGame LatestBeaconRound (r:1 w:0)
+	/// Storage: CoinGame BeaconWagers (r:1 w:1)
+	/// Storage: CoinGame EscrowTimeouts (r:1 w:1)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Storage: CoinGame Escrows (r:0 w:1)
+	/// The range of component `s` is `[0, 63]`.
+	fn double_or_nothing(s: u32, ) -> Weight {
+		Weight::from_parts(95_000_000, 20_000)
+			.saturating_add(Weight::from_parts(500_000, 32).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(19_u64))
+			.saturating_add(T::DbWeight::get().writes(17_u64))
+	}
+	/// Storage: CoinGame RoundBeaconRounds (r:1 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: CoinGame LatestBeaconRound (r:1 w:0)
+	/// Storage: CoinGame NextTossId (r:1 w:1)
+	/// Storage: CoinGame BeaconWagers (r:1 w:1)
+	/// Storage: CoinGame RoundEntries (r:1 w:1)
+	/// Storage: CoinGame SelfExclusions (r:1 w:1)
+	/// Storage: CoinGame PendingPlayerLimits (r:1 w:1)
+	/// Storage: CoinGame PlayerLimits (r:1 w:1)
+	/// Storage: CoinGame Losses (r:1 w:1)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: CoinGame EscrowTimeouts (r:1 w:1)
+	/// Storage: CoinGame Escrows (r:0 w:1)
+	/// The range of component `e` is `[0, 255]`.
+	fn join_round(e: u32, ) -> Weight {
+		Weight::from_parts(70_000_000, 16_000)
+			.saturating_add(Weight::from_parts(600_000, 49).saturating_mul(e.into()))
+			.saturating_add(T::DbWeight::get().reads(14_u64))
+			.saturating_add(T::DbWeight::get().writes(12_u64))
+	}
+	/// Storage: CoinGame NextTournamentId (r:1 w:1)
+	/// Storage: CoinGame ActiveTournaments (r:1 w:1)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: CoinGame Tournaments (r:0 w:1)
+	fn create_tournament() -> Weight {
+		Weight::from_parts(45_000_000, 6_000)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: CoinGame Tournaments (r:1 w:1)
+	/// Storage: CoinGame TournamentPlayers (r:1 w:1)
+	/// Storage: CoinGame SelfExclusions (r:1 w:1)
+	/// Storage: CoinGame PendingPlayerLimits (r:1 w:1)
+	/// Storage: CoinGame PlayerLimits (r:1 w:1)
+	/// Storage: CoinGame Losses (r:1 w:1)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: CoinGame EscrowTimeouts (r:1 w:1)
+	/// Storage: CoinGame Escrows (r:0 w:1)
+	/// The range of component `p` is `[0, 63]`.
+	fn register_for_tournament(p: u32, ) -> Weight {
+		Weight::from_parts(55_000_000, 12_000)
+			.saturating_add(Weight::from_parts(500_000, 32).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().writes(10_u64))
+	}
+	/// Storage: CoinGame Tournaments (r:1 w:1)
+	/// Storage: CoinGame TournamentPlayers (r:1 w:1)
+	/// Storage: CoinGame Escrows (r:64 w:64)
+	/// Storage: Balances Holds (r:65 w:65)
+	/// Storage: System Account (r:65 w:65)
+	/// Storage: CoinGame Losses (r:64 w:64)
+	/// Storage: CoinGame ActiveTournaments (r:1 w:1)
+	/// The range of component `p` is `[0, 64]`.
+	fn cancel_tournament(p: u32, ) -> Weight {
+		Weight::from_parts(40_000_000, 6_000)
+			.saturating_add(Weight::from_parts(25_000_000, 5_000).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+			.saturating_add(T::DbWeight::get().reads((4_u64).saturating_mul(p.into())))
+			.saturating_add(T::DbWeight::get().writes((4_u64).saturating_mul(p.into())))
+	}
+	/// Storage: CoinGame LotteryTickets (r:1 w:1)
+	/// Storage: CoinGame SelfExclusions (r:1 w:1)
+	/// Storage: CoinGame PendingPlayerLimits (r:1 w:1)
+	/// Storage: CoinGame PlayerLimits (r:1 w:1)
+	/// Storage: CoinGame Losses (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: CoinGame Committed (r:1 w:1)
+	/// Storage: CoinGame LotteryPot (r:1 w:1)
+	/// The range of component `c` is `[1, 1000]`.
+	fn buy_tickets(c: u32, ) -> Weight {
+		Weight::from_parts(45_000_000, 8_000)
+			.saturating_add(Weight::from_parts(1_500_000, 32).saturating_mul(c.into()))
+			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
+	}
+	/// Storage: CoinGame TotalShares (r:1 w:1)
+	/// Storage: CoinGame LiquidityShares (r:1 w:1)
+	/// Storage: CoinGame Committed (r:1 w:0)
+	/// Storage: System Account (r:2 w:2)
+	fn fund_house() -> Weight {
+		Weight::from_parts(45_000_000, 6_000)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: CoinGame TotalShares (r:1 w:1)
+	/// Storage: CoinGame LiquidityShares (r:1 w:1)
+	/// Storage: CoinGame Committed (r:1 w:0)
+	/// Storage: System Account (r:2 w:2)
+	fn withdraw_from_house() -> Weight {
+		Weight::from_parts(45_000_000, 6_000)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: CoinGame Committed (r:1 w:0)
+	/// Storage: System Account (r:1 w:0)
+	/// Storage: CoinGame WageringPaused (r:0 w:1)
+	fn set_wagering_paused() -> Weight {
+		Weight::from_parts(15_000_000, 3_000)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: CoinGame TotalShares (r:1 w:1)
+	/// Storage: CoinGame LiquidityShares (r:2 w:2)
+	/// Storage: CoinGame Committed (r:1 w:0)
+	/// Storage: System Account (r:2 w:2)
+	fn provide_liquidity() -> Weight {
+		Weight::from_parts(50_000_000, 8_000)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: CoinGame LiquidityWithdrawals (r:1 w:1)
+	/// Storage: CoinGame LiquidityShares (r:1 w:1)
+	/// Storage: CoinGame TotalShares (r:1 w:0)
+	/// Storage: CoinGame Committed (r:1 w:0)
+	/// Storage: System Account (r:1 w:0)
+	fn withdraw_liquidity() -> Weight {
+		Weight::from_parts(40_000_000, 8_000)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: CoinGame LiquidityWithdrawals (r:1 w:1)
+	/// Storage: CoinGame LiquidityShares (r:1 w:1)
+	/// Storage: CoinGame TotalShares (r:1 w:1)
+	/// Storage: CoinGame Committed (r:1 w:0)
+	/// Storage: System Account (r:2 w:2)
+	fn redeem_liquidity() -> Weight {
+		Weight::from_parts(45_000_000, 8_000)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: CoinGame CoinStorage (r:1 w:0)
+	/// Storage: CoinGame TossActivities (r:1 w:1)
+	/// Storage: CoinGame CoinLastActivity (r:0 w:1)
+	/// Storage: CoinGame AssetBetLimits (r:1 w:0)
+	/// Storage: CoinGame WageringPaused (r:1 w:0)
+	/// Storage: Assets Asset (r:1 w:1)
+	/// Storage: Assets Account (r:2 w:2)
+	/// Storage: CoinGame AssetCommitted (r:1 w:1)
+	/// Storage: CoinGame SelfExclusions (r:1 w:1)
+	/// Storage: CoinGame PendingPlayerLimits (r:1 w:1)
+	/// Storage: CoinGame PlayerLimits (r:1 w:1)
+	/// Storage: CoinGame Losses (r:1 w:1)
+	/// Storage: CoinGame NextTossId (r:1 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: CoinGame LatestBeaconRound (r:1 w:0)
+	/// Storage: CoinGame BeaconWagers (r:1 w:1)
+	/// Storage: CoinGame EscrowTimeouts (r:1 w:1)
+	fn toss_coin_with_asset() -> Weight {
+		Weight::from_parts(85_000_000, 20_000)
+			.saturating_add(T::DbWeight::get().reads(18_u64))
+			.saturating_add(T::DbWeight::get().writes(14_u64))
+	}
+	/// Storage: CoinGame AssetBetLimits (r:0 w:1)
+	fn set_asset_bet_limits() -> Weight {
+		Weight::from_parts(15_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Assets Asset (r:1 w:1)
+	/// Storage: Assets Account (r:2 w:2)
+	/// Storage: System Account (r:1 w:1)
+	fn fund_asset_house() -> Weight {
+		Weight::from_parts(50_000_000, 9_000)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: Assets Asset (r:1 w:1)
+	/// Storage: Assets Account (r:2 w:2)
+	/// Storage: CoinGame AssetCommitted (r:1 w:0)
+	/// Storage: System Account (r:1 w:1)
+	fn withdraw_asset_from_house() -> Weight {
+		Weight::from_parts(50_000_000, 9_000)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: CoinGame PendingPlayerLimits (r:1 w:1)
+	/// Storage: CoinGame PlayerLimits (r:1 w:1)
+	fn set_play_limits() -> Weight {
+		Weight::from_parts(25_000_000, 5_000)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: CoinGame SelfExclusions (r:1 w:1)
+	fn self_exclude() -> Weight {
+		Weight::from_parts(20_000_000, 2_600)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: CoinGame Claimable (r:1 w:1)
+	/// Storage: CoinGame VestingClaimable (r:1 w:1)
+	/// Storage: CoinGame PayoutVestingRule (r:1 w:0)
+	/// Storage: Vesting Vesting (r:1 w:1)
+	/// Storage: Balances Locks (r:1 w:1)
+	/// Storage: CoinGame Committed (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	fn claim() -> Weight {
+		Weight::from_parts(80_000_000, 12_000)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	/// Storage: CoinGame PayoutVestingRule (r:0 w:1)
+	fn set_payout_vesting() -> Weight {
+		Weight::from_parts(15_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: CoinGame Streaks (r:1 w:1)
+	/// Storage: CoinGame StreakExpiries (r:1 w:1)
+	/// Storage: CoinGame Committed (r:1 w:1)
+	/// Storage: CoinGame Claimable (r:1 w:1)
+	/// Storage: CoinGame VestingClaimable (r:1 w:1)
+	/// Storage: CoinGame PayoutVestingRule (r:1 w:0)
+	/// Storage: CoinGame WageringPaused (r:1 w:1)
+	/// Storage: CoinGame Losses (r:1 w:1)
+	/// Storage: System Account (r:1 w:0)
+	/// The range of component `s` is `[0, 63]`.
+	fn cash_out_streak(s: u32, ) -> Weight {
+		Weight::from_parts(55_000_000, 10_000)
+			.saturating_add(Weight::from_parts(500_000, 32).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
 	}
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
-	fn default_weight() -> Weight {
-		Weight::default()
+	/// Storage: CoinGame CoinStorage (r:1 w:1)
+	/// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: CoinGame CoinLastActivity (r:0 w:1)
+	fn create_coin() -> Weight {
+		Weight::from_parts(45_000_000, 7_000)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: CoinGame CoinStorage (r:1 w:0)
+	/// Storage: CoinGame TossActivities (r:1 w:1)
+	/// Storage: CoinGame CoinLastActivity (r:0 w:1)
+	/// Storage: CoinGame WageringPaused (r:1 w:0)
+	/// Storage: CoinGame Committed (r:1 w:1)
+	/// Storage: CoinGame SelfExclusions (r:1 w:1)
+	/// Storage: CoinGame PendingPlayerLimits (r:1 w:1)
+	/// Storage: CoinGame PlayerLimits (r:1 w:1)
+	/// Storage: CoinGame Losses (r:1 w:1)
+	/// Storage: System Account (r:3 w:3)
+	/// Storage: CoinGame FreeTossesUsed (r:1 w:1)
+	/// Storage: CoinGame NextTossId (r:1 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: CoinGame LatestBeaconRound (r:1 w:0)
+	/// Storage: CoinGame BeaconWagers (r:1 w:1)
+	/// Storage: CoinGame EscrowTimeouts (r:1 w:1)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Storage: CoinGame Escrows (r:0 w:1)
+	fn toss_coin() -> Weight {
+		Weight::from_parts(85_000_000, 20_000)
+			.saturating_add(RocksDbWeight::get().reads(18_u64))
+			.saturating_add(RocksDbWeight::get().writes(16_u64))
+	}
+	/// Storage: CoinGame CoinStorage (r:1 w:0)
+	/// Storage: CoinGame TossActivities (r:1 w:1)
+	/// Storage: CoinGame CoinLastActivity (r:0 w:1)
+	/// Storage: CoinGame WageringPaused (r:1 w:0)
+	/// Storage: CoinGame Committed (r:1 w:1)
+	/// Storage: CoinGame SelfExclusions (r:1 w:1)
+	/// Storage: CoinGame PendingPlayerLimits (r:1 w:1)
+	/// Storage: CoinGame PlayerLimits (r:1 w:1)
+	/// Storage: CoinGame Losses (r:1 w:1)
+	/// Storage: System Account (r:3 w:3)
+	/// Storage: CoinGame NextTossId (r:1 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: CoinGame LatestBeaconRound (r:1 w:0)
+	/// Storage: CoinGame BeaconWagers (r:1 w:1)
+	/// Storage: CoinGame EscrowTimeouts (r:1 w:1)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Storage: CoinGame Escrows (r:0 w:1)
+	/// The range of component `b` is `[1, 50]`.
+	fn toss_many(b: u32, ) -> Weight {
+		Weight::from_parts(85_000_000, 20_000)
+			.saturating_add(Weight::from_parts(4_000_000, 0).saturating_mul(b.into()))
+			.saturating_add(RocksDbWeight::get().reads(17_u64))
+			.saturating_add(RocksDbWeight::get().writes(15_u64))
+	}
+	/// Storage: CoinGame CoinStorage (r:1 w:1)
+	/// Storage: CoinGame CoinLastActivity (r:0 w:1)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn remove_coin() -> Weight {
+		Weight::from_parts(40_000_000, 7_000)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: CoinGame CoinStorage (r:1 w:0)
+	/// Storage: CoinGame TossActivities (r:1 w:1)
+	/// Storage: CoinGame CoinLastActivity (r:0 w:1)
+	/// Storage: CoinGame NextTossId (r:1 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: CoinGame LatestBeaconRound (r:1 w:0)
+	/// Storage: CoinGame BeaconWagers (r:1 w:1)
+	/// Storage: CoinGame EscrowTimeouts (r:1 w:1)
+	fn toss_coin_with_beacon() -> Weight {
+		Weight::from_parts(45_000_000, 16_000)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: CoinGame BeaconWagers (r:1 w:1)
+	/// Storage: CoinGame LatestBeaconRound (r:1 w:1)
+	/// Storage: CoinGame Committed (r:1 w:1)
+	/// Storage: CoinGame Claimable (r:1 w:1)
+	/// Storage: CoinGame VestingClaimable (r:1 w:1)
+	/// Storage: CoinGame PayoutVestingRule (r:1 w:0)
+	/// Storage: CoinGame WageringPaused (r:1 w:1)
+	/// Storage: CoinGame Losses (r:1 w:1)
+	/// Storage: CoinGame JackpotStreaks (r:1 w:1)
+	/// Storage: CoinGame JackpotPot (r:1 w:1)
+	/// Storage: CoinGame CoinStorage (r:1 w:1)
+	/// Storage: CoinGame Escrows (r:1 w:1)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: CoinGame RoundEntries (r:1 w:1)
+	/// Storage: CoinGame RoundResults (r:0 w:1)
+	/// The range of component `t` is `[1, 512]`.
+	fn submit_beacon_pulse(t: u32, ) -> Weight {
+		Weight::from_parts(45_000_000_000, 12_695)
+			.saturating_add(Weight::from_parts(60_000_000, 2_603).saturating_mul(t.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((11_u64).saturating_mul(t.into())))
+			.saturating_add(RocksDbWeight::get().writes((9_u64).saturating_mul(t.into())))
+	}
+	/// Storage: CoinGame TossActivities (r:1 w:1)
+	/// Storage: CoinGame CoinLastActivity (r:0 w:1)
+	/// Storage: CoinGame WageringPaused (r:1 w:0)
+	/// Storage: CoinGame Committed (r:1 w:1)
+	/// Storage: CoinGame SelfExclusions (r:1 w:1)
+	/// Storage: CoinGame PendingPlayerLimits (r:1 w:1)
+	/// Storage: CoinGame PlayerLimits (r:1 w:1)
+	/// Storage: CoinGame Losses (r:1 w:1)
+	/// Storage: System Account (r:3 w:3)
+	/// Storage: CoinGame NextTossId (r:1 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: CoinGame LatestBeaconRound (r:1 w:0)
+	/// Storage: CoinGame BeaconWagers (r:1 w:1)
+	/// Storage: CoinGame EscrowTimeouts (r:1 w:1)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Storage: CoinGame Escrows (r:0 w:1)
+	fn roll() -> Weight {
+		Weight::from_parts(80_000_000, 20_000)
+			.saturating_add(RocksDbWeight::get().reads(16_u64))
+			.saturating_add(RocksDbWeight::get().writes(15_u64))
+	}
+	/// Storage: CoinGame OpenSeries (r:1 w:1)
+	/// Storage: CoinGame WageringPaused (r:1 w:0)
+	/// Storage: CoinGame Committed (r:1 w:1)
+	/// Storage: CoinGame SelfExclusions (r:1 w:1)
+	/// Storage: CoinGame PendingPlayerLimits (r:1 w:1)
+	/// Storage: CoinGame PlayerLimits (r:1 w:1)
+	/// Storage: CoinGame Losses (r:1 w:1)
+	/// Storage: System Account (r:3 w:3)
+	/// Storage: CoinGame EscrowTimeouts (r:1 w:1)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Storage: CoinGame Escrows (r:0 w:1)
+	fn open_series() -> Weight {
+		Weight::from_parts(65_000_000, 14_000)
+			.saturating_add(RocksDbWeight::get().reads(12_u64))
+			.saturating_add(RocksDbWeight::get().writes(12_u64))
+	}
+	/// Storage: CoinGame OpenSeries (r:1 w:0)
+	/// Storage: CoinGame SeriesTosses (r:1 w:1)
+	/// Storage: CoinGame TossActivities (r:1 w:1)
+	/// Storage: CoinGame CoinLastActivity (r:0 w:1)
+	/// Storage: CoinGame NextTossId (r:1 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: CoinGame LatestBeaconRound (r:1 w:0)
+	/// Storage: CoinGame BeaconWagers (r:1 w:1)
+	/// Storage: CoinGame EscrowTimeouts (r:1 w:1)
+	fn toss_series() -> Weight {
+		Weight::from_parts(45_000_000, 16_000)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	/// Storage: CoinGame CoinStorage (r:1 w:0)
+	/// Storage: CoinGame StreakTosses (r:1 w:1)
+	/// Storage: CoinGame CoinLastActivity (r:0 w:1)
+	/// Storage: CoinGame Streaks (r:1 w:1)
+	/// Storage: CoinGame StreakExpiries (r:1 w:1)
+	/// Storage: CoinGame WageringPaused (r:1 w:0)
+	/// Storage: CoinGame Committed (r:1 w:1)
+	/// Storage: CoinGame SelfExclusions (r:1 w:1)
+	/// Storage: CoinGame PendingPlayerLimits (r:1 w:1)
+	/// Storage: CoinGame PlayerLimits (r:1 w:1)
+	/// Storage: CoinGame Losses (r:1 w:1)
+	/// Storage: System Account (r:3 w:3)
+	/// Storage: CoinGame NextTossId (r:1 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: CoinGame LatestBeaconRound (r:1 w:0)
+	/// Storage: CoinGame BeaconWagers (r:1 w:1)
+	/// Storage: CoinGame EscrowTimeouts (r:1 w:1)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Storage: CoinGame Escrows (r:0 w:1)
+	/// The range of component `s` is `[0, 63]`.
+	fn double_or_nothing(s: u32, ) -> Weight {
+		Weight::from_parts(95_000_000, 20_000)
+			.saturating_add(Weight::from_parts(500_000, 32).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(19_u64))
+			.saturating_add(RocksDbWeight::get().writes(17_u64))
+	}
+	/// Storage: CoinGame RoundBeaconRounds (r:1 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: CoinGame LatestBeaconRound (r:1 w:0)
+	/// Storage: CoinGame NextTossId (r:1 w:1)
+	/// Storage: CoinGame BeaconWagers (r:1 w:1)
+	/// Storage: CoinGame RoundEntries (r:1 w:1)
+	/// Storage: CoinGame SelfExclusions (r:1 w:1)
+	/// Storage: CoinGame PendingPlayerLimits (r:1 w:1)
+	/// Storage: CoinGame PlayerLimits (r:1 w:1)
+	/// Storage: CoinGame Losses (r:1 w:1)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: CoinGame EscrowTimeouts (r:1 w:1)
+	/// Storage: CoinGame Escrows (r:0 w:1)
+	/// The range of component `e` is `[0, 255]`.
+	fn join_round(e: u32, ) -> Weight {
+		Weight::from_parts(70_000_000, 16_000)
+			.saturating_add(Weight::from_parts(600_000, 49).saturating_mul(e.into()))
+			.saturating_add(RocksDbWeight::get().reads(14_u64))
+			.saturating_add(RocksDbWeight::get().writes(12_u64))
+	}
+	/// Storage: CoinGame NextTournamentId (r:1 w:1)
+	/// Storage: CoinGame ActiveTournaments (r:1 w:1)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: CoinGame Tournaments (r:0 w:1)
+	fn create_tournament() -> Weight {
+		Weight::from_parts(45_000_000, 6_000)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: CoinGame Tournaments (r:1 w:1)
+	/// Storage: CoinGame TournamentPlayers (r:1 w:1)
+	/// Storage: CoinGame SelfExclusions (r:1 w:1)
+	/// Storage: CoinGame PendingPlayerLimits (r:1 w:1)
+	/// Storage: CoinGame PlayerLimits (r:1 w:1)
+	/// Storage: CoinGame Losses (r:1 w:1)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: CoinGame EscrowTimeouts (r:1 w:1)
+	/// Storage: CoinGame Escrows (r:0 w:1)
+	/// The range of component `p` is `[0, 63]`.
+	fn register_for_tournament(p: u32, ) -> Weight {
+		Weight::from_parts(55_000_000, 12_000)
+			.saturating_add(Weight::from_parts(500_000, 32).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().writes(10_u64))
+	}
+	/// Storage: CoinGame Tournaments (r:1 w:1)
+	/// Storage: CoinGame TournamentPlayers (r:1 w:1)
+	/// Storage: CoinGame Escrows (r:64 w:64)
+	/// Storage: Balances Holds (r:65 w:65)
+	/// Storage: System Account (r:65 w:65)
+	/// Storage: CoinGame Losses (r:64 w:64)
+	/// Storage: CoinGame ActiveTournaments (r:1 w:1)
+	/// The range of component `p` is `[0, 64]`.
+	fn cancel_tournament(p: u32, ) -> Weight {
+		Weight::from_parts(40_000_000, 6_000)
+			.saturating_add(Weight::from_parts(25_000_000, 5_000).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+			.saturating_add(RocksDbWeight::get().reads((4_u64).saturating_mul(p.into())))
+			.saturating_add(RocksDbWeight::get().writes((4_u64).saturating_mul(p.into())))
+	}
+	/// Storage: CoinGame LotteryTickets (r:1 w:1)
+	/// Storage: CoinGame SelfExclusions (r:1 w:1)
+	/// Storage: CoinGame PendingPlayerLimits (r:1 w:1)
+	/// Storage: CoinGame PlayerLimits (r:1 w:1)
+	/// Storage: CoinGame Losses (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: CoinGame Committed (r:1 w:1)
+	/// Storage: CoinGame LotteryPot (r:1 w:1)
+	/// The range of component `c` is `[1, 1000]`.
+	fn buy_tickets(c: u32, ) -> Weight {
+		Weight::from_parts(45_000_000, 8_000)
+			.saturating_add(Weight::from_parts(1_500_000, 32).saturating_mul(c.into()))
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
+	}
+	/// Storage: CoinGame TotalShares (r:1 w:1)
+	/// Storage: CoinGame LiquidityShares (r:1 w:1)
+	/// Storage: CoinGame Committed (r:1 w:0)
+	/// Storage: System Account (r:2 w:2)
+	fn fund_house() -> Weight {
+		Weight::from_parts(45_000_000, 6_000)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: CoinGame TotalShares (r:1 w:1)
+	/// Storage: CoinGame LiquidityShares (r:1 w:1)
+	/// Storage: CoinGame Committed (r:1 w:0)
+	/// Storage: System Account (r:2 w:2)
+	fn withdraw_from_house() -> Weight {
+		Weight::from_parts(45_000_000, 6_000)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: CoinGame Committed (r:1 w:0)
+	/// Storage: System Account (r:1 w:0)
+	/// Storage: CoinGame WageringPaused (r:0 w:1)
+	fn set_wagering_paused() -> Weight {
+		Weight::from_parts(15_000_000, 3_000)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: CoinGame TotalShares (r:1 w:1)
+	/// Storage: CoinGame LiquidityShares (r:2 w:2)
+	/// Storage: CoinGame Committed (r:1 w:0)
+	/// Storage: System Account (r:2 w:2)
+	fn provide_liquidity() -> Weight {
+		Weight::from_parts(50_000_000, 8_000)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: CoinGame LiquidityWithdrawals (r:1 w:1)
+	/// Storage: CoinGame LiquidityShares (r:1 w:1)
+	/// Storage: CoinGame TotalShares (r:1 w:0)
+	/// Storage: CoinGame Committed (r:1 w:0)
+	/// Storage: System Account (r:1 w:0)
+	fn withdraw_liquidity() -> Weight {
+		Weight::from_parts(40_000_000, 8_000)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: CoinGame LiquidityWithdrawals (r:1 w:1)
+	/// Storage: CoinGame LiquidityShares (r:1 w:1)
+	/// Storage: CoinGame TotalShares (r:1 w:1)
+	/// Storage: CoinGame Committed (r:1 w:0)
+	/// Storage: System Account (r:2 w:2)
+	fn redeem_liquidity() -> Weight {
+		Weight::from_parts(45_000_000, 8_000)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: CoinGame CoinStorage (r:1 w:0)
+	/// Storage: CoinGame TossActivities (r:1 w:1)
+	/// Storage: CoinGame CoinLastActivity (r:0 w:1)
+	/// Storage: CoinGame AssetBetLimits (r:1 w:0)
+	/// Storage: CoinGame WageringPaused (r:1 w:0)
+	/// Storage: Assets Asset (r:1 w:1)
+	/// Storage: Assets Account (r:2 w:2)
+	/// Storage: CoinGame AssetCommitted (r:1 w:1)
+	/// Storage: CoinGame SelfExclusions (r:1 w:1)
+	/// Storage: CoinGame PendingPlayerLimits (r:1 w:1)
+	/// Storage: CoinGame PlayerLimits (r:1 w:1)
+	/// Storage: CoinGame Losses (r:1 w:1)
+	/// Storage: CoinGame NextTossId (r:1 w:1)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: CoinGame LatestBeaconRound (r:1 w:0)
+	/// Storage: CoinGame BeaconWagers (r:1 w:1)
+	/// Storage: CoinGame EscrowTimeouts (r:1 w:1)
+	fn toss_coin_with_asset() -> Weight {
+		Weight::from_parts(85_000_000, 20_000)
+			.saturating_add(RocksDbWeight::get().reads(18_u64))
+			.saturating_add(RocksDbWeight::get().writes(14_u64))
+	}
+	/// Storage: CoinGame AssetBetLimits (r:0 w:1)
+	fn set_asset_bet_limits() -> Weight {
+		Weight::from_parts(15_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Assets Asset (r:1 w:1)
+	/// Storage: Assets Account (r:2 w:2)
+	/// Storage: System Account (r:1 w:1)
+	fn fund_asset_house() -> Weight {
+		Weight::from_parts(50_000_000, 9_000)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: Assets Asset (r:1 w:1)
+	/// Storage: Assets Account (r:2 w:2)
+	/// Storage: CoinGame AssetCommitted (r:1 w:0)
+	/// Storage: System Account (r:1 w:1)
+	fn withdraw_asset_from_house() -> Weight {
+		Weight::from_parts(50_000_000, 9_000)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: CoinGame PendingPlayerLimits (r:1 w:1)
+	/// Storage: CoinGame PlayerLimits (r:1 w:1)
+	fn set_play_limits() -> Weight {
+		Weight::from_parts(25_000_000, 5_000)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: CoinGame SelfExclusions (r:1 w:1)
+	fn self_exclude() -> Weight {
+		Weight::from_parts(20_000_000, 2_600)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: CoinGame Claimable (r:1 w:1)
+	/// Storage: CoinGame VestingClaimable (r:1 w:1)
+	/// Storage: CoinGame PayoutVestingRule (r:1 w:0)
+	/// Storage: Vesting Vesting (r:1 w:1)
+	/// Storage: Balances Locks (r:1 w:1)
+	/// Storage: CoinGame Committed (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	fn claim() -> Weight {
+		Weight::from_parts(80_000_000, 12_000)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	/// Storage: CoinGame PayoutVestingRule (r:0 w:1)
+	fn set_payout_vesting() -> Weight {
+		Weight::from_parts(15_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: CoinGame Streaks (r:1 w:1)
+	/// Storage: CoinGame StreakExpiries (r:1 w:1)
+	/// Storage: CoinGame Committed (r:1 w:1)
+	/// Storage: CoinGame Claimable (r:1 w:1)
+	/// Storage: CoinGame VestingClaimable (r:1 w:1)
+	/// Storage: CoinGame PayoutVestingRule (r:1 w:0)
+	/// Storage: CoinGame WageringPaused (r:1 w:1)
+	/// Storage: CoinGame Losses (r:1 w:1)
+	/// Storage: System Account (r:1 w:0)
+	/// The range of component `s` is `[0, 63]`.
+	fn cash_out_streak(s: u32, ) -> Weight {
+		Weight::from_parts(55_000_000, 10_000)
+			.saturating_add(Weight::from_parts(500_000, 32).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
 	}
 }
@@ -6,6 +6,7 @@
 #[cfg(feature = "std")]
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
+use codec::Encode;
 use pallet_grandpa::AuthorityId as GrandpaId;
 use sp_api::impl_runtime_apis;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
@@ -13,9 +14,10 @@ use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
 	traits::{
-		AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, NumberFor, One, Verify,
+		AccountIdLookup, BlakeTwo256, Block as BlockT, ConvertInto, Extrinsic as ExtrinsicT,
+		IdentifyAccount, NumberFor, One, SaturatedConversion, Verify,
 	},
-	transaction_validity::{TransactionSource, TransactionValidity},
+	transaction_validity::{TransactionPriority, TransactionSource, TransactionValidity},
 	ApplyExtrinsicResult, MultiSignature,
 };
 use sp_std::prelude::*;
@@ -39,6 +41,8 @@ pub use frame_support::{
 	StorageValue,
 };
 pub use frame_system::Call as SystemCall;
+use frame_support::traits::{AsEnsureOriginWithArg, WithdrawReasons};
+use frame_system::{EnsureRoot, EnsureRootWithSuccess, EnsureSigned};
 pub use pallet_balances::Call as BalancesCall;
 pub use pallet_timestamp::Call as TimestampCall;
 use pallet_transaction_payment::{ConstFeeMultiplier, CurrencyAdapter, Multiplier};
@@ -107,10 +111,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 100,
+	spec_version: 101,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 	state_version: 1,
 };
 
@@ -193,7 +197,7 @@ impl frame_system::Config for Runtime {
 	/// What to do if a new account is created.
 	type OnNewAccount = ();
 	/// What to do if an account is fully reaped from the system.
-	type OnKilledAccount = ();
+	type OnKilledAccount = CoinGame;
 	/// The data to be stored in an account.
 	type AccountData = pallet_balances::AccountData<Balance>;
 	/// Weight information for the extrinsics of this pallet.
@@ -248,8 +252,34 @@ impl pallet_balances::Config for Runtime {
 	type WeightInfo = pallet_balances::weights::SubstrateWeight<Runtime>;
 	type FreezeIdentifier = ();
 	type MaxFreezes = ();
-	type RuntimeHoldReason = ();
-	type MaxHolds = ();
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type MaxHolds = ConstU32<3>;
+}
+
+/// Identifier of an asset.
+pub type AssetId = u32;
+
+impl pallet_assets::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = Balance;
+	type RemoveItemsLimit = ConstU32<1000>;
+	type AssetId = AssetId;
+	type AssetIdParameter = codec::Compact<AssetId>;
+	type Currency = Balances;
+	type CreateOrigin = AsEnsureOriginWithArg<EnsureSigned<AccountId>>;
+	type ForceOrigin = EnsureRoot<AccountId>;
+	type AssetDeposit = ConstU128<{ 1_000 * EXISTENTIAL_DEPOSIT }>;
+	type AssetAccountDeposit = ConstU128<EXISTENTIAL_DEPOSIT>;
+	type MetadataDepositBase = ConstU128<{ 10 * EXISTENTIAL_DEPOSIT }>;
+	type MetadataDepositPerByte = ConstU128<EXISTENTIAL_DEPOSIT>;
+	type ApprovalDeposit = ConstU128<EXISTENTIAL_DEPOSIT>;
+	type StringLimit = ConstU32<50>;
+	type Freezer = ();
+	type Extra = ();
+	type CallbackHandle = ();
+	type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
 }
 
 parameter_types! {
@@ -258,6 +288,65 @@ parameter_types! {
 
 parameter_types! {
 	pub const CoinFlipperPalletId: PalletId = PalletId(*b"coinflip");
+	/// HTTP endpoint of the drand mainnet.
+	pub const BeaconEndpoint: &'static str = "https://api.drand.sh";
+	/// Public key of the drand mainnet (chained) network.
+	pub const DrandPublicKey: [u8; 48] = hex_literal::hex!(
+		"868f005eb8e6e4ca0a47c8a77ceaa5309a47978a7c71bc5cce96366b5d7a569937c529eeda66c7293784a9402801af31"
+	);
+	pub const BeaconUnsignedPriority: TransactionPriority = TransactionPriority::max_value() / 2;
+	/// Share of the losers' pool of a global round kept by the house.
+	pub const RoundFee: Permill = Permill::from_percent(2);
+	/// Share of a tournament prize pool paid to the champion.
+	pub const TournamentWinnerShare: Permill = Permill::from_percent(70);
+	/// Share of the lottery pot kept by the house.
+	pub const LotteryHouseCut: Permill = Permill::from_percent(5);
+	/// Largest stake on a single house-backed wager, as a share of the available bankroll.
+	pub const MaxBetFraction: Permill = Permill::from_percent(1);
+	/// Share of every losing coin toss stake added to the jackpot.
+	pub const JackpotContribution: Permill = Permill::from_percent(2);
+	/// Share of every house-backed stake sent to the treasury.
+	pub const Rake: Permill = Permill::from_percent(1);
+}
+
+parameter_types! {
+	pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
+	pub const ProposalBond: Permill = Permill::from_percent(5);
+	pub const MaxTreasurySpend: Balance = Balance::MAX;
+}
+
+impl pallet_treasury::Config for Runtime {
+	type PalletId = TreasuryPalletId;
+	type Currency = Balances;
+	type ApproveOrigin = EnsureRoot<AccountId>;
+	type RejectOrigin = EnsureRoot<AccountId>;
+	type RuntimeEvent = RuntimeEvent;
+	type OnSlash = Treasury;
+	type ProposalBond = ProposalBond;
+	type ProposalBondMinimum = ConstU128<{ 100 * EXISTENTIAL_DEPOSIT }>;
+	type ProposalBondMaximum = ();
+	type SpendPeriod = ConstU32<DAYS>;
+	type Burn = ();
+	type BurnDestination = ();
+	type SpendFunds = ();
+	type WeightInfo = pallet_treasury::weights::SubstrateWeight<Runtime>;
+	type MaxApprovals = ConstU32<100>;
+	type SpendOrigin = EnsureRootWithSuccess<AccountId, MaxTreasurySpend>;
+}
+
+parameter_types! {
+	pub UnvestedFundsAllowedWithdrawReasons: WithdrawReasons =
+		WithdrawReasons::except(WithdrawReasons::TRANSFER | WithdrawReasons::RESERVE);
+}
+
+impl pallet_vesting::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type BlockNumberToBalance = ConvertInto;
+	type MinVestedTransfer = ConstU128<{ 100 * EXISTENTIAL_DEPOSIT }>;
+	type WeightInfo = pallet_vesting::weights::SubstrateWeight<Runtime>;
+	type UnvestedFundsAllowedWithdrawReasons = UnvestedFundsAllowedWithdrawReasons;
+	const MAX_VESTING_SCHEDULES: u32 = 28;
 }
 
 impl pallet_transaction_payment::Config for Runtime {
@@ -277,12 +366,135 @@ impl pallet_sudo::Config for Runtime {
 
 impl pallet_insecure_randomness_collective_flip::Config for Runtime {}
 
-/// Configure the pallet-template in pallets/template.
+/// Configure the coin game in pallets/coin-game.
 impl pallet_coin_game::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = pallet_coin_game::weights::SubstrateWeight<Runtime>;
 	type PalletId = CoinFlipperPalletId;
 	type Randomness = RandomnessCollectiveFlip;
+	type Currency = Balances;
+	type Assets = Assets;
+	type NativeBalance = Balances;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type Vesting = Vesting;
+	type CoinDeposit = ConstU128<{ 100 * EXISTENTIAL_DEPOSIT }>;
+	type CoinExpiry = ConstU32<{ 30 * DAYS }>;
+	type AdminOrigin = EnsureRoot<AccountId>;
+	type MaxBetFraction = MaxBetFraction;
+	type SolvencyBuffer = ConstU128<{ 10_000 * EXISTENTIAL_DEPOSIT }>;
+	type CircuitBreakerThreshold = ConstU128<{ 1_000 * EXISTENTIAL_DEPOSIT }>;
+	type Rake = Rake;
+	type RakeDestination = Treasury;
+	type LiquidityWithdrawalDelay = ConstU32<DAYS>;
+	type LiquidityRedemptionWindow = ConstU32<DAYS>;
+	type MinimumLiquidity = ConstU128<{ 1_000 * EXISTENTIAL_DEPOSIT }>;
+	type EscrowTimeout = ConstU32<DAYS>;
+	type MaxEscrowTimeouts = ConstU32<1_024>;
+	type MaxSides = ConstU32<100>;
+	type MaxBatch = ConstU32<50>;
+	type TossCooldown = ConstU32<1>;
+	type TossPeriod = ConstU32<HOURS>;
+	type MaxTossesPerPeriod = ConstU32<600>;
+	type FreeTosses = ConstU32<10>;
+	type FreeTossPeriod = ConstU32<DAYS>;
+	type DayLength = ConstU32<DAYS>;
+	type LimitLooseningDelay = ConstU32<{ 7 * DAYS }>;
+	type MaxSeriesLength = ConstU32<9>;
+	type SeriesExpiry = ConstU32<DAYS>;
+	type MaxStreakLength = ConstU32<10>;
+	type StreakExpiry = ConstU32<HOURS>;
+	type MaxExpiringStreaks = ConstU32<64>;
+	type RoundLength = ConstU32<{ 5 * MINUTES }>;
+	type RoundFee = RoundFee;
+	type MaxRoundEntries = ConstU32<256>;
+	type MaxTournamentPlayers = ConstU32<64>;
+	type MaxActiveTournaments = ConstU32<8>;
+	type TournamentDeposit = ConstU128<{ 100 * EXISTENTIAL_DEPOSIT }>;
+	type MaxRegistrationPeriod = ConstU32<DAYS>;
+	type TournamentWinnerShare = TournamentWinnerShare;
+	type LotteryPeriod = ConstU32<HOURS>;
+	type TicketPrice = ConstU128<{ 10 * EXISTENTIAL_DEPOSIT }>;
+	type MaxTickets = ConstU32<1_000>;
+	type LotteryHouseCut = LotteryHouseCut;
+	type JackpotContribution = JackpotContribution;
+	type JackpotStreak = ConstU32<10>;
+	type MinJackpotStake = ConstU128<{ 10 * EXISTENTIAL_DEPOSIT }>;
+	type AuthorityId = pallet_coin_game::crypto::BeaconAuthId;
+	type BeaconVerifier = pallet_coin_game::beacon::DrandChainedVerifier<DrandPublicKey>;
+	type UnixTime = Timestamp;
+	type BeaconEndpoint = BeaconEndpoint;
+	/// Genesis time and period of the drand mainnet.
+	type BeaconGenesisTime = ConstU64<1_595_431_050>;
+	type BeaconPeriod = ConstU64<30>;
+	type BeaconRoundDelay = ConstU64<2>;
+	type ExpectedBlockTime = ConstU64<MILLISECS_PER_BLOCK>;
+	type MaxBeaconWagers = ConstU32<64>;
+	type MaxBeaconTosses = ConstU32<512>;
+	type BeaconUnsignedPriority = BeaconUnsignedPriority;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = CoinGameBenchmarkHelper;
+}
+
+/// Creates the asset wagered in the coin game benchmarks.
+#[cfg(feature = "runtime-benchmarks")]
+pub struct CoinGameBenchmarkHelper;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_coin_game::BenchmarkHelper<AssetId, AccountId> for CoinGameBenchmarkHelper {
+	fn create_asset(owner: &AccountId) -> AssetId {
+		use frame_support::traits::fungibles::Create;
+
+		let asset = AssetId::MAX;
+		<Assets as Create<AccountId>>::create(asset, owner.clone(), true, 1)
+			.expect("asset is not created yet");
+		asset
+	}
+}
+
+impl<C> frame_system::offchain::CreateSignedTransaction<C> for Runtime
+where
+	RuntimeCall: From<C>,
+{
+	fn create_transaction<A: frame_system::offchain::AppCrypto<Self::Public, Self::Signature>>(
+		call: RuntimeCall,
+		public: <Signature as Verify>::Signer,
+		account: AccountId,
+		nonce: Nonce,
+	) -> Option<(RuntimeCall, <UncheckedExtrinsic as ExtrinsicT>::SignaturePayload)> {
+		let period =
+			BlockHashCount::get().checked_next_power_of_two().map(|c| c / 2).unwrap_or(2) as u64;
+		// The `System::block_number` is initialized with `n+1`, so the actual block number is `n`.
+		let current_block = System::block_number().saturated_into::<u64>().saturating_sub(1);
+		let extra: SignedExtra = (
+			frame_system::CheckNonZeroSender::<Runtime>::new(),
+			frame_system::CheckSpecVersion::<Runtime>::new(),
+			frame_system::CheckTxVersion::<Runtime>::new(),
+			frame_system::CheckGenesis::<Runtime>::new(),
+			frame_system::CheckEra::<Runtime>::from(generic::Era::mortal(period, current_block)),
+			frame_system::CheckNonce::<Runtime>::from(nonce),
+			frame_system::CheckWeight::<Runtime>::new(),
+			pallet_transaction_payment::ChargeTransactionPayment::<Runtime>::from(0),
+			pallet_coin_game::CheckCoinGame::<Runtime>::new(),
+		);
+		let raw_payload = SignedPayload::new(call, extra).ok()?;
+		let signature = raw_payload.using_encoded(|payload| A::sign(payload, public))?;
+		let address = Address::Id(account);
+		let (call, extra, _) = raw_payload.deconstruct();
+		Some((call, (address, signature, extra)))
+	}
+}
+
+impl frame_system::offchain::SigningTypes for Runtime {
+	type Public = <Signature as Verify>::Signer;
+	type Signature = Signature;
+}
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Runtime
+where
+	RuntimeCall: From<C>,
+{
+	type OverarchingCall = RuntimeCall;
+	type Extrinsic = UncheckedExtrinsic;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -293,8 +505,11 @@ construct_runtime!(
 		Aura: pallet_aura,
 		Grandpa: pallet_grandpa,
 		Balances: pallet_balances,
+		Assets: pallet_assets,
 		TransactionPayment: pallet_transaction_payment,
 		Sudo: pallet_sudo,
+		Treasury: pallet_treasury,
+		Vesting: pallet_vesting,
 		CoinGame: pallet_coin_game,
 		RandomnessCollectiveFlip: pallet_insecure_randomness_collective_flip,
 	}
@@ -316,6 +531,7 @@ pub type SignedExtra = (
 	frame_system::CheckNonce<Runtime>,
 	frame_system::CheckWeight<Runtime>,
 	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+	pallet_coin_game::CheckCoinGame<Runtime>,
 );
 
 /// Unchecked extrinsic type as expected by this runtime.
@@ -323,6 +539,9 @@ pub type UncheckedExtrinsic =
 	generic::UncheckedExtrinsic<Address, RuntimeCall, Signature, SignedExtra>;
 /// The payload being signed in transactions.
 pub type SignedPayload = generic::SignedPayload<RuntimeCall, SignedExtra>;
+/// Storage migrations run on the next runtime upgrade.
+pub type Migrations = (pallet_coin_game::migrations::v1::MigrateToV1<Runtime>,);
+
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
 	Runtime,
@@ -330,6 +549,7 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	Migrations,
 >;
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -342,9 +562,12 @@ mod benches {
 		[frame_benchmarking, BaselineBench::<Runtime>]
 		[frame_system, SystemBench::<Runtime>]
 		[pallet_balances, Balances]
+		[pallet_assets, Assets]
 		[pallet_timestamp, Timestamp]
 		[pallet_sudo, Sudo]
-		[pallet_template, TemplateModule]
+		[pallet_treasury, Treasury]
+		[pallet_vesting, Vesting]
+		[pallet_coin_game, CoinGame]
 	);
 }
 
@@ -472,6 +695,12 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_coin_game_runtime_api::CoinGameApi<Block, AccountId, Balance> for Runtime {
+		fn claimable(who: AccountId) -> Balance {
+			pallet_coin_game::Claimable::<Runtime>::get(who)
+		}
+	}
+
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance> for Runtime {
 		fn query_info(
 			uxt: <Block as BlockT>::Extrinsic,